@@ -1,5 +1,8 @@
 use {
-    bevycraft_core::prelude::{Block, PatternContainer, Registrar, RegistrarOps, Registry},
+    bevycraft_core::{
+        blocks::register_blocks,
+        prelude::{Block, PackingLayout, PatternContainer, Registrar, RegistrarOps, Registry},
+    },
     criterion::{criterion_group, criterion_main, Criterion},
     std::hint::black_box,
 };
@@ -43,8 +46,90 @@ pub fn compression(c: &mut Criterion) {
     println!("  - Bit capacity: {}", container.bit_capacity());
 }
 
+pub fn dense(c: &mut Criterion) {
+    let mut rand = fastrand::Rng::new();
+
+    let values: [u32; CHUNK_SIZE] = std::array::from_fn(|_| rand.u32(..512));
+    let container: PatternContainer<u32, CHUNK_SIZE> = PatternContainer::from_dense(&values);
+
+    let mut out = [0u32; CHUNK_SIZE];
+
+    c.bench_function("PatternContainer/FromDense", |b| {
+        b.iter(|| {
+            black_box(PatternContainer::<u32, CHUNK_SIZE>::from_dense(black_box(
+                &values,
+            )))
+        })
+    });
+
+    c.bench_function("PatternContainer/UnpackInto", |b| {
+        b.iter(|| {
+            container.unpack_into(&mut out);
+            black_box(&out);
+        })
+    });
+
+    c.bench_function("PatternContainer/GetAll", |b| {
+        b.iter(|| {
+            for (index, value) in out.iter_mut().enumerate() {
+                *value = *container.get(index).unwrap();
+            }
+
+            black_box(&out);
+        })
+    });
+}
+
+pub fn layouts(c: &mut Criterion) {
+    let mut rand = fastrand::Rng::new();
+
+    // 6 bits per entry, so packed entries straddle words and aligned ones leave 4 bits unused.
+    let values: [u32; CHUNK_SIZE] = std::array::from_fn(|_| rand.u32(..48));
+
+    let mut out = [0u32; CHUNK_SIZE];
+
+    for (name, layout) in [
+        ("Packed", PackingLayout::Packed),
+        ("Aligned", PackingLayout::Aligned),
+    ] {
+        let mut container: PatternContainer<u32, CHUNK_SIZE> =
+            PatternContainer::from_dense_with_layout(&values, layout);
+
+        c.bench_function(&format!("PatternContainer/{name}/RandomRead"), |b| {
+            b.iter(|| {
+                let index = rand.usize(..CHUNK_SIZE);
+
+                black_box(container.get(index));
+            })
+        });
+
+        c.bench_function(&format!("PatternContainer/{name}/Iter"), |b| {
+            b.iter(|| black_box(container.iter().fold(0, |acc, value| acc ^ value)))
+        });
+
+        c.bench_function(&format!("PatternContainer/{name}/UnpackInto"), |b| {
+            b.iter(|| {
+                container.unpack_into(&mut out);
+                black_box(&out);
+            })
+        });
+
+        c.bench_function(&format!("PatternContainer/{name}/RandomWrite"), |b| {
+            b.iter(|| {
+                let index = rand.usize(..CHUNK_SIZE);
+                let value = rand.u32(..48);
+
+                container.set(index, value);
+            })
+        });
+    }
+}
+
 fn registry_access(c: &mut Criterion) {
-    let registry = &*Registrar::<Block>::read_from_registry();
+    let mut registry = Registrar::<Block>::isolated();
+    register_blocks(&mut registry).unwrap();
+
+    let registry = &registry;
     let n = registry.len();
 
     let mut rand = fastrand::Rng::new();
@@ -58,5 +143,5 @@ fn registry_access(c: &mut Criterion) {
     });
 }
 
-criterion_group!(benches, registry_access);
+criterion_group!(benches, registry_access, dense, layouts);
 criterion_main!(benches);
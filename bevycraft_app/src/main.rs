@@ -5,6 +5,7 @@ use bevy_dylib;
 use {
     bevy::{
         anti_alias::fxaa::Fxaa,
+        asset::io::file::FileAssetReader,
         camera::Exposure,
         camera_controller::free_camera::{FreeCamera, FreeCameraPlugin},
         core_pipeline::tonemapping::Tonemapping,
@@ -22,7 +23,7 @@ use {
         tasks::available_parallelism,
     },
     bevycraft_app::{systems::plugins::DebugHudPlugin, *},
-    bevycraft_core::prelude::*,
+    bevycraft_core::{blocks::register_blocks, prelude::*},
     bevycraft_render::prelude::*,
     bevycraft_world::prelude::*,
     ron::{extensions::Extensions, Options},
@@ -57,10 +58,31 @@ fn main() -> AppExit {
             ),
             ChunkRenderPlugin::new(AppState::InGame),
             DebugHudPlugin,
+            RegistryPlugin::new()
+                .with::<Block>()
+                .with::<Item>()
+                .with::<Recipe>(),
         ))
         .init_state::<AppState>()
         .insert_resource(Time::<Fixed>::from_hz(64.0))
         .insert_resource(AssetsLoading::default())
+        .register_blocks(register_blocks)
+        .add_systems(
+            RegistryStartup,
+            (
+                (load_block_definitions, generate_block_items, load_recipes)
+                    .chain()
+                    .in_set(RegistrySet::Load),
+                (
+                    load_block_tags,
+                    load_item_tags,
+                    load_loot_tables,
+                    build_recipe_book,
+                )
+                    .chain()
+                    .in_set(RegistrySet::Bind),
+            ),
+        )
         .add_systems(OnEnter(AppState::ModelDiscovery), discover_models)
         .add_systems(OnEnter(AppState::BuildArrayTexture), build_array_texture)
         .add_systems(
@@ -71,6 +93,96 @@ fn main() -> AppExit {
         .run()
 }
 
+fn load_block_definitions() {
+    let assets = FileAssetReader::get_base_path().join("assets");
+
+    let mut blocks = Registrar::<Block>::write_to_registry()
+        .expect("Block registry was frozen before registration");
+
+    match register_block_definitions(&mut *blocks, &assets) {
+        Ok(count) => info!("Loaded {} block definitions", count),
+        Err(e) => panic!("Failed to load block definitions: {}", e),
+    }
+}
+
+fn generate_block_items() {
+    let blocks = Registrar::<Block>::read_from_registry();
+
+    let mut items = Registrar::<Item>::write_to_registry()
+        .expect("Item registry was frozen before registration");
+
+    match register_block_items(&*blocks, &mut *items) {
+        Ok(count) => info!("Generated {} block items", count),
+        Err(e) => panic!("Failed to generate block items: {}", e),
+    }
+}
+
+fn load_recipes() {
+    let assets = FileAssetReader::get_base_path().join("assets");
+
+    let items = Registrar::<Item>::read_from_registry();
+
+    let mut recipes = Registrar::<Recipe>::write_to_registry()
+        .expect("Recipe registry was frozen before registration");
+
+    match register_recipes(&mut *recipes, &*items, &assets) {
+        Ok(count) => info!("Loaded {} recipes", count),
+        Err(e) => panic!("Failed to load recipes: {}", e),
+    }
+}
+
+fn load_block_tags() {
+    let assets = FileAssetReader::get_base_path().join("assets");
+
+    let mut blocks = Registrar::<Block>::write_to_registry()
+        .expect("Block registry was frozen before registration");
+
+    match load_tags(&*blocks, &assets, "blocks") {
+        Ok(tags) => {
+            info!("Loaded {} block tags", tags.len());
+            blocks.bind_tags(tags);
+        }
+        Err(e) => panic!("Failed to load block tags: {}", e),
+    }
+}
+
+fn load_item_tags() {
+    let assets = FileAssetReader::get_base_path().join("assets");
+
+    let mut items = Registrar::<Item>::write_to_registry()
+        .expect("Item registry was frozen before registration");
+
+    match load_tags(&*items, &assets, "items") {
+        Ok(tags) => {
+            info!("Loaded {} item tags", tags.len());
+            items.bind_tags(tags);
+        }
+        Err(e) => panic!("Failed to load item tags: {}", e),
+    }
+}
+
+fn load_loot_tables(mut commands: Commands) {
+    let assets = FileAssetReader::get_base_path().join("assets");
+
+    let blocks = Registrar::<Block>::read_from_registry();
+    let items = Registrar::<Item>::read_from_registry();
+
+    match load_block_loot_tables(&*blocks, &*items, &assets) {
+        Ok(tables) => {
+            info!("Loaded loot tables");
+            commands.insert_resource(tables);
+        }
+        Err(e) => panic!("Failed to load loot tables: {}", e),
+    }
+}
+
+fn build_recipe_book(mut commands: Commands) {
+    let recipes = Registrar::<Recipe>::read_from_registry();
+    let items = Registrar::<Item>::read_from_registry();
+
+    commands.insert_resource(RecipeBook::build(&*recipes, &*items));
+}
+
 fn discover_models(
     mut commands: Commands,
     mut state: ResMut<NextState<AppState>>,
@@ -82,7 +194,7 @@ fn discover_models(
 
     let mut manager = ModelManager::<Block, BlockModel>::with_capacity(blocks.len());
 
-    blocks.iter().for_each(|(block_key, block)| {
+    blocks.iter_indexed().for_each(|(idx, block_key, block)| {
         if block.air() {
             return;
         }
@@ -95,7 +207,7 @@ fn discover_models(
                 .set(Extensions::IMPLICIT_SOME, true);
         });
 
-        manager.set(blocks.key_to_idx(block_key).unwrap(), h);
+        manager.set(idx, h);
     });
 
     commands.insert_resource(manager);
@@ -26,8 +26,30 @@ pub struct BlockBehaviour {
     #[public]
     #[default(BlockFlags::empty())]
     pub(super) flags: BlockFlags,
+
+    /// Block light emitted, from `0` to [`MAX_LIGHT`].
+    #[into]
+    #[public]
+    #[default(0)]
+    pub(super) light_emission: u8,
+
+    /// Light lost when passing through, from `0` (transparent) to [`MAX_LIGHT`] (opaque).
+    #[into]
+    #[public]
+    #[default(MAX_LIGHT)]
+    pub(super) light_opacity: u8,
+
+    /// Signal sent to connecting neighbors, from `0` to [`MAX_SIGNAL`].
+    #[into]
+    #[public]
+    #[default(0)]
+    pub(super) signal_emission: u8,
 }
 
+pub const MAX_LIGHT: u8 = 15;
+
+pub const MAX_SIGNAL: u8 = 15;
+
 impl Default for BlockBehaviour {
     #[inline(always)]
     fn default() -> Self {
@@ -36,6 +58,7 @@ impl Default for BlockBehaviour {
             .toughness(0.0)
             .friction(0.0)
             .flags(BlockFlags::AIR)
+            .light_opacity(0)
             .build()
     }
 }
@@ -86,11 +109,6 @@ impl BlockBehaviour {
         self.flags.contains(BlockFlags::REPLACEABLE)
     }
 
-    #[inline(always)]
-    pub const fn can_support(&self) -> bool {
-        self.flags.contains(BlockFlags::CAN_SUPPORT)
-    }
-
     #[inline(always)]
     pub const fn does_connect(&self) -> bool {
         self.flags.contains(BlockFlags::DOES_CONNECT)
@@ -110,4 +128,19 @@ impl BlockBehaviour {
     pub const fn passable(&self) -> bool {
         self.flags.contains(BlockFlags::PASSABLE)
     }
+
+    #[inline(always)]
+    pub const fn light_emission(&self) -> u8 {
+        self.light_emission
+    }
+
+    #[inline(always)]
+    pub const fn light_opacity(&self) -> u8 {
+        self.light_opacity
+    }
+
+    #[inline(always)]
+    pub const fn signal_emission(&self) -> u8 {
+        self.signal_emission
+    }
 }
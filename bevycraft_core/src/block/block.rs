@@ -12,6 +12,26 @@ pub struct Block {
     #[public]
     #[default(BlockShape::None)]
     shape: BlockShape,
+
+    #[into]
+    #[public]
+    #[default(StateDefinition::default())]
+    states: StateDefinition,
+
+    #[into]
+    #[public]
+    #[default(None)]
+    block_entity: Option<BlockEntityType>,
+
+    #[into]
+    #[public]
+    #[default(None)]
+    ticker: Option<&'static dyn BlockTicker>,
+
+    #[into]
+    #[public]
+    #[default(None)]
+    callbacks: Option<&'static dyn BlockCallbacks>,
 }
 
 impl Default for Block {
@@ -20,6 +40,10 @@ impl Default for Block {
         Self {
             behaviour: BlockBehaviour::default(),
             shape: BlockShape::None,
+            states: StateDefinition::default(),
+            block_entity: None,
+            ticker: None,
+            callbacks: None,
         }
     }
 }
@@ -71,11 +95,6 @@ impl Block {
         self.behaviour.flags.contains(BlockFlags::REPLACEABLE)
     }
 
-    #[inline(always)]
-    pub const fn can_support(&self) -> bool {
-        self.behaviour.flags.contains(BlockFlags::CAN_SUPPORT)
-    }
-
     #[inline(always)]
     pub const fn does_connect(&self) -> bool {
         self.behaviour.flags.contains(BlockFlags::DOES_CONNECT)
@@ -96,8 +115,73 @@ impl Block {
         self.behaviour.flags.contains(BlockFlags::PASSABLE)
     }
 
+    #[inline(always)]
+    pub const fn fluid(&self) -> bool {
+        self.behaviour.flags.contains(BlockFlags::FLUID)
+    }
+
+    #[inline(always)]
+    pub const fn falls(&self) -> bool {
+        self.behaviour.flags.contains(BlockFlags::FALLS)
+    }
+
+    #[inline(always)]
+    pub const fn flags(&self) -> BlockFlags {
+        self.behaviour.flags
+    }
+
+    #[inline(always)]
+    pub const fn light_emission(&self) -> u8 {
+        self.behaviour.light_emission
+    }
+
+    #[inline(always)]
+    pub const fn light_opacity(&self) -> u8 {
+        self.behaviour.light_opacity
+    }
+
+    #[inline(always)]
+    pub const fn signal_emission(&self) -> u8 {
+        self.behaviour.signal_emission
+    }
+
     #[inline(always)]
     pub const fn shapes(&self) -> &BlockShape {
         &self.shape
     }
+
+    #[inline(always)]
+    pub const fn states(&self) -> &StateDefinition {
+        &self.states
+    }
+
+    #[inline(always)]
+    pub const fn block_entity(&self) -> Option<&BlockEntityType> {
+        self.block_entity.as_ref()
+    }
+
+    #[inline(always)]
+    pub const fn ticker(&self) -> Option<&'static dyn BlockTicker> {
+        self.ticker
+    }
+
+    #[inline(always)]
+    pub const fn callbacks(&self) -> Option<&'static dyn BlockCallbacks> {
+        self.callbacks
+    }
+
+    /// Takes the code-side parts of `other`, which data files cannot describe: its block entity,
+    /// ticker and callbacks, and its states when `self` declares none.
+    #[inline]
+    pub fn with_hooks_of(mut self, other: &Block) -> Self {
+        if self.states.properties().is_empty() {
+            self.states = other.states.clone();
+        }
+
+        self.block_entity = other.block_entity;
+        self.ticker = other.ticker;
+        self.callbacks = other.callbacks;
+
+        self
+    }
 }
@@ -2,11 +2,14 @@ use {
     crate::{
         consts::{FULL_BLOCK, FULL_SHAPE, SLAB_SHAPE, STAIR_SHAPE, TRAPDOOR_SHAPE},
         prelude::*,
+        properties::{AXIS, FACING, FALLING, HALF, LEVEL, OPEN, POWER, POWERED, WATERLOGGED},
     },
     bevycraft_macros::*,
 };
 
 context! {
+    pub fn register_blocks;
+
     pub static AIR: Block = "air" => || Block::default();
 
     pub static GRASS_BLOCK: Block = "grass_block" => || Block::new()
@@ -18,6 +21,7 @@ context! {
                 .build()
         )
         .shape(FULL_SHAPE)
+        .ticker(&GRASS_SPREADING as &dyn BlockTicker)
         .build();
 
     pub static DIRT: Block = "dirt" => || Block::new()
@@ -36,7 +40,7 @@ context! {
             BlockBehaviour::new()
                 .hardness(0.5)
                 .toughness(0.5)
-                .flags(*FULL_BLOCK)
+                .flags(*FULL_BLOCK | BlockFlags::FALLS)
                 .build()
         )
         .shape(FULL_SHAPE)
@@ -47,7 +51,7 @@ context! {
             BlockBehaviour::new()
                 .hardness(0.5)
                 .toughness(0.5)
-                .flags(*FULL_BLOCK)
+                .flags(*FULL_BLOCK | BlockFlags::FALLS)
                 .build()
         )
         .shape(FULL_SHAPE)
@@ -58,10 +62,14 @@ context! {
             BlockBehaviour::new()
                 .hardness(0.0)
                 .toughness(0.0)
-                .flags(*FULL_BLOCK)
+                .flags(BlockFlags::FLUID | BlockFlags::REPLACEABLE | BlockFlags::SEE_THROUGH)
+                .light_opacity(2)
                 .build()
         )
         .shape(BlockShape::None)
+        .states([LEVEL.key(), FALLING.key()])
+        .ticker(&WATER_FLOW as &dyn BlockTicker)
+        .callbacks(&WATER_FLOW as &dyn BlockCallbacks)
         .build();
 
     pub static SNOW_BLOCK: Block = "snow_block" => || Block::new()
@@ -102,7 +110,7 @@ context! {
             BlockBehaviour::new()
                 .hardness(f32::INFINITY)
                 .toughness(f32::INFINITY)
-                .flags(BlockFlags::OCCLUDABLE | BlockFlags::COLLIDABLE | BlockFlags::CAN_SUPPORT)
+                .flags(BlockFlags::OCCLUDABLE | BlockFlags::COLLIDABLE)
                 .build()
         )
         .shape(FULL_SHAPE)
@@ -117,6 +125,7 @@ context! {
                 .build()
         )
         .shape(FULL_SHAPE)
+        .states([AXIS.key()])
         .build();
 
     pub static OAK_PLANKS: Block = "oak_planks" => || Block::new()
@@ -135,10 +144,12 @@ context! {
             BlockBehaviour::new()
                 .hardness(2.0)
                 .toughness(3.0)
-                .flags(BlockFlags::COLLIDABLE | BlockFlags::OCCLUDABLE | BlockFlags::CAN_SUPPORT)
+                .flags(BlockFlags::COLLIDABLE | BlockFlags::OCCLUDABLE)
+                .light_opacity(0)
                 .build()
         )
         .shape(SLAB_SHAPE)
+        .states([HALF.key(), WATERLOGGED.key()])
         .build();
 
     pub static OAK_PLANKS_STAIR: Block = "oak_planks_stair" => || Block::new()
@@ -146,10 +157,12 @@ context! {
             BlockBehaviour::new()
                 .hardness(2.0)
                 .toughness(3.0)
-                .flags(BlockFlags::COLLIDABLE | BlockFlags::OCCLUDABLE | BlockFlags::CAN_SUPPORT)
+                .flags(BlockFlags::COLLIDABLE | BlockFlags::OCCLUDABLE)
+                .light_opacity(0)
                 .build()
         )
         .shape(STAIR_SHAPE)
+        .states([FACING.key(), HALF.key(), WATERLOGGED.key()])
         .build();
 
     pub static OAK_TRAPDOOR: Block = "oak_trapdoor" => || Block::new()
@@ -157,10 +170,13 @@ context! {
             BlockBehaviour::new()
                 .hardness(2.0)
                 .toughness(3.0)
-                .flags(BlockFlags::COLLIDABLE | BlockFlags::OCCLUDABLE)
+                .flags(BlockFlags::COLLIDABLE | BlockFlags::OCCLUDABLE | BlockFlags::DOES_CONNECT)
+                .light_opacity(0)
                 .build()
         )
         .shape(TRAPDOOR_SHAPE)
+        .states([FACING.key(), HALF.key(), OPEN.key(), POWERED.key(), WATERLOGGED.key()])
+        .callbacks(&OpenOnSignal as &dyn BlockCallbacks)
         .build();
 
     pub static OAK_LEAVES: Block = "oak_leaves" => || Block::new()
@@ -169,6 +185,7 @@ context! {
                 .hardness(0.2)
                 .toughness(0.2)
                 .flags(BlockFlags::COLLIDABLE | BlockFlags::OCCLUDABLE)
+                .light_opacity(1)
                 .build()
         )
         .shape(FULL_SHAPE)
@@ -180,6 +197,7 @@ context! {
                 .hardness(0.0)
                 .toughness(0.0)
                 .flags(BlockFlags::empty())
+                .light_opacity(0)
                 .build()
         )
         .build();
@@ -190,7 +208,44 @@ context! {
                 .hardness(0.0)
                 .toughness(0.0)
                 .flags(BlockFlags::empty())
+                .light_opacity(0)
                 .build()
         )
         .build();
+
+    pub static SIGNAL_BLOCK: Block = "signal_block" => || Block::new()
+        .behaviour(
+            BlockBehaviour::new()
+                .hardness(1.5)
+                .toughness(6.0)
+                .flags(*FULL_BLOCK | BlockFlags::DOES_CONNECT)
+                .signal_emission(MAX_SIGNAL)
+                .build()
+        )
+        .shape(FULL_SHAPE)
+        .build();
+
+    pub static SIGNAL_WIRE: Block = "signal_wire" => || Block::new()
+        .behaviour(
+            BlockBehaviour::new()
+                .hardness(0.0)
+                .toughness(0.0)
+                .flags(BlockFlags::DOES_CONNECT)
+                .light_opacity(0)
+                .build()
+        )
+        .states([POWER.key()])
+        .ticker(&SignalConductor as &dyn BlockTicker)
+        .callbacks(&SignalConductor as &dyn BlockCallbacks)
+        .build();
 }
+
+static GRASS_SPREADING: SpreadingTicker = SpreadingTicker {
+    target: || DIRT.default_state(),
+    decay: || DIRT.default_state(),
+};
+
+static WATER_FLOW: FlowingFluid = FlowingFluid {
+    block: || *WATER,
+    tick_delay: 5,
+};
@@ -0,0 +1,67 @@
+use {
+    crate::{
+        prelude::{BlockLevel, BlockStates},
+        properties::OPEN,
+    },
+    bevy::math::IVec3,
+    std::fmt::Debug,
+};
+
+/// Reacts to world edits made through [`BlockLevel::place_block`], [`BlockLevel::break_block`]
+/// and [`BlockLevel::use_block`].
+pub trait BlockCallbacks: Debug + Send + Sync {
+    /// Called after `state` was placed at `position`.
+    #[allow(unused_variables)]
+    fn on_place(&self, level: &mut dyn BlockLevel, position: IVec3, state: usize) {}
+
+    /// Called before the block at `position` is broken.
+    #[allow(unused_variables)]
+    fn on_break(&self, level: &mut dyn BlockLevel, position: IVec3, state: usize) {}
+
+    /// Called after the block at `neighbor`, adjacent to `position`, changed.
+    #[allow(unused_variables)]
+    fn on_neighbor_changed(
+        &self,
+        level: &mut dyn BlockLevel,
+        position: IVec3,
+        state: usize,
+        neighbor: IVec3,
+    ) {
+    }
+
+    /// Called when the block is used, returning whether the use was handled.
+    #[allow(unused_variables)]
+    fn on_use(&self, level: &mut dyn BlockLevel, position: IVec3, state: usize) -> bool {
+        false
+    }
+}
+
+impl PartialEq for dyn BlockCallbacks {
+    #[inline(always)]
+    fn eq(&self, other: &Self) -> bool {
+        std::ptr::addr_eq(self, other)
+    }
+}
+
+/// Toggles the `open` property of the block when used, e.g. for doors and trapdoors.
+#[derive(Debug)]
+pub struct OpenOnUse;
+
+impl BlockCallbacks for OpenOnUse {
+    fn on_use(&self, level: &mut dyn BlockLevel, position: IVec3, state: usize) -> bool {
+        let states = BlockStates::global();
+
+        let Some(open) = states.value(state, OPEN) else {
+            return false;
+        };
+
+        let Some(toggled) = states.with(state, OPEN, !open) else {
+            return false;
+        };
+
+        level.set_state(position, toggled);
+        level.notify_neighbors(position);
+
+        true
+    }
+}
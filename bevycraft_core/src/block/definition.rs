@@ -0,0 +1,413 @@
+use {
+    crate::{
+        prelude::*,
+        properties::ALL_PROPERTIES,
+        util::files::{collect_files, read_dir_sorted, relative_asset_path},
+    },
+    bevy::math::{Vec3A, bounding::Aabb3d},
+    serde::Deserialize,
+    std::{
+        fs,
+        path::{Path, PathBuf},
+    },
+    thiserror::Error,
+};
+
+const BLOCKS_DIRECTORY: &str = "blocks";
+
+const DEFINITION_EXTENSION: &str = "ron";
+
+/// A block as described by a `<namespace>/blocks/*.ron` file.
+///
+/// Omitted fields fall back to the same defaults as [`BlockBehaviour::new`].
+#[derive(Deserialize, Debug, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct BlockDefinition {
+    #[serde(default = "default_hardness")]
+    pub hardness: f32,
+
+    #[serde(default = "default_toughness")]
+    pub toughness: f32,
+
+    #[serde(default = "default_friction")]
+    pub friction: f32,
+
+    #[serde(default)]
+    pub bounciness: f32,
+
+    #[serde(default)]
+    pub flags: Vec<Box<str>>,
+
+    #[serde(default)]
+    pub light_emission: u8,
+
+    #[serde(default = "default_light_opacity")]
+    pub light_opacity: u8,
+
+    #[serde(default)]
+    pub signal_emission: u8,
+
+    #[serde(default)]
+    pub shape: Vec<BoxDefinition>,
+
+    #[serde(default)]
+    pub properties: Vec<Box<str>>,
+}
+
+#[derive(Deserialize, Debug, Clone, Copy)]
+#[serde(deny_unknown_fields)]
+pub struct BoxDefinition {
+    pub min: [f32; 3],
+    pub max: [f32; 3],
+}
+
+impl BlockDefinition {
+    pub fn resolve(&self, location: &AssetLocation) -> Result<Block, BlockDefinitionError> {
+        let mut flags = BlockFlags::empty();
+
+        for flag in &self.flags {
+            let Some(parsed) = BlockFlags::from_name(&flag.to_ascii_uppercase()) else {
+                return Err(BlockDefinitionError::UnknownFlag {
+                    location: location.clone(),
+                    flag: flag.to_string(),
+                });
+            };
+
+            flags |= parsed;
+        }
+
+        let mut bboxes = Vec::with_capacity(self.shape.len());
+
+        for (index, bbox) in self.shape.iter().enumerate() {
+            bboxes.push(bbox.resolve(location, index)?);
+        }
+
+        let mut properties = Vec::with_capacity(self.properties.len());
+
+        for name in &self.properties {
+            let Some(property) = ALL_PROPERTIES.iter().find(|p| p.name() == name.as_ref()) else {
+                return Err(BlockDefinitionError::UnknownProperty {
+                    location: location.clone(),
+                    property: name.to_string(),
+                });
+            };
+
+            properties.push(*property);
+        }
+
+        Ok(Block::new()
+            .behaviour(
+                BlockBehaviour::new()
+                    .hardness(self.hardness)
+                    .toughness(self.toughness)
+                    .friction(self.friction)
+                    .bounciness(self.bounciness)
+                    .flags(flags)
+                    .light_emission(self.light_emission.min(MAX_LIGHT))
+                    .light_opacity(self.light_opacity.min(MAX_LIGHT))
+                    .signal_emission(self.signal_emission.min(MAX_SIGNAL))
+                    .build(),
+            )
+            .shape(bboxes.into_boxed_slice())
+            .states(properties.into_boxed_slice())
+            .build())
+    }
+}
+
+impl BoxDefinition {
+    fn resolve(
+        &self,
+        location: &AssetLocation,
+        index: usize,
+    ) -> Result<Aabb3d, BlockDefinitionError> {
+        let min = Vec3A::from_array(self.min);
+        let max = Vec3A::from_array(self.max);
+
+        let malformed = |reason: &'static str| BlockDefinitionError::MalformedShape {
+            location: location.clone(),
+            index,
+            reason,
+        };
+
+        if !min.is_finite() || !max.is_finite() {
+            return Err(malformed("coordinates must be finite"));
+        }
+
+        if min.cmpgt(max).any() {
+            return Err(malformed("`min` must not exceed `max` on any axis"));
+        }
+
+        if min.cmplt(Vec3A::ZERO).any() || max.cmpgt(Vec3A::ONE).any() {
+            return Err(malformed("box must lie within the unit block"));
+        }
+
+        Ok(Aabb3d { min, max })
+    }
+}
+
+/// Reads every `<namespace>/blocks/**/*.ron` file under `assets`, keyed by namespace and
+/// relative path without extension (e.g. `bevycraft/blocks/dirt.ron` becomes `bevycraft:dirt`).
+///
+/// Definitions are returned sorted by key so registration order does not depend on the
+/// file system.
+pub fn load_block_definitions(
+    assets: &Path,
+) -> Result<Vec<(AssetLocation, Block)>, BlockDefinitionError> {
+    let mut definitions = Vec::new();
+
+    for namespace in read_dir_sorted(assets)? {
+        let directory = namespace.join(BLOCKS_DIRECTORY);
+
+        if !directory.is_dir() {
+            continue;
+        }
+
+        let Some(namespace) = namespace.file_name().and_then(|n| n.to_str()) else {
+            continue;
+        };
+
+        let mut files = Vec::new();
+        collect_files(&directory, DEFINITION_EXTENSION, &mut files)?;
+
+        for file in files {
+            let relative = relative_asset_path(&directory, &file).unwrap();
+
+            let location = AssetLocation::try_with_custom_namespace(namespace, &relative)?;
+
+            let bytes = fs::read(&file)?;
+
+            let definition = ron::de::from_bytes::<BlockDefinition>(&bytes)
+                .map_err(|e| BlockDefinitionError::Deserialize(file.clone(), Box::new(e)))?;
+
+            let block = definition.resolve(&location)?;
+
+            definitions.push((location, block));
+        }
+    }
+
+    definitions.sort_by(|(a, _), (b, _)| a.cmp(b));
+
+    Ok(definitions)
+}
+
+/// Loads all block definitions under `assets` into `registry`.
+///
+/// A definition whose key is already registered replaces the data of the existing block in
+/// place, so code-defined [`Holder`]s keep resolving to the same index, and the block keeps its
+/// states, block entity, ticker and callbacks. Such a definition may omit `properties`, but
+/// must otherwise declare the same properties as the existing block, in the same order.
+/// Returns the number of definitions applied.
+pub fn register_block_definitions(
+    registry: &mut impl Registry<Block>,
+    assets: &Path,
+) -> Result<usize, BlockDefinitionError> {
+    let definitions = load_block_definitions(assets)?;
+    let count = definitions.len();
+
+    for (location, block) in definitions {
+        let block = match registry.get_by_key(&location) {
+            Some(existing) => {
+                let declared = block.states().properties();
+                let expected = existing.states().properties();
+
+                if !declared.is_empty() && declared != expected {
+                    return Err(BlockDefinitionError::PropertyMismatch {
+                        location,
+                        expected: expected.iter().map(PropertyKey::name).collect(),
+                        found: declared.iter().map(PropertyKey::name).collect(),
+                    });
+                }
+
+                block.with_hooks_of(existing)
+            }
+            None => block,
+        };
+
+        registry
+            .register_or_replace(location.clone(), block)
+            .map_err(|e| BlockDefinitionError::Registration(location, e))?;
+    }
+
+    Ok(count)
+}
+
+#[inline(always)]
+const fn default_hardness() -> f32 {
+    1.0
+}
+
+#[inline(always)]
+const fn default_toughness() -> f32 {
+    1.0
+}
+
+#[inline(always)]
+const fn default_friction() -> f32 {
+    0.6
+}
+
+#[inline(always)]
+const fn default_light_opacity() -> u8 {
+    MAX_LIGHT
+}
+
+#[derive(Error, Debug)]
+pub enum BlockDefinitionError {
+    #[error("io error: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("failed to deserialize `{path}`: {error}", path = .0.display(), error = .1)]
+    Deserialize(PathBuf, Box<ron::de::SpannedError>),
+
+    #[error("invalid location: {0}")]
+    InvalidLocation(#[from] AssetLocationError),
+
+    #[error("unknown block flag `{flag}` in `{location}`")]
+    UnknownFlag { location: AssetLocation, flag: String },
+
+    #[error("unknown block property `{property}` in `{location}`")]
+    UnknownProperty {
+        location: AssetLocation,
+        property: String,
+    },
+
+    #[error("malformed shape box #{index} in `{location}`: {reason}")]
+    MalformedShape {
+        location: AssetLocation,
+        index: usize,
+        reason: &'static str,
+    },
+
+    #[error("properties {found:?} of `{location}` do not match its properties {expected:?}")]
+    PropertyMismatch {
+        location: AssetLocation,
+        expected: Box<[&'static str]>,
+        found: Box<[&'static str]>,
+    },
+
+    #[error("failed to register `{0}`: {1}")]
+    Registration(AssetLocation, RegistrationError),
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::{blocks::register_blocks, properties::*},
+    };
+
+    /// Writes each `(path, definition)` as a block definition of the default namespace under a
+    /// fresh assets directory named after `test`.
+    fn write_definitions(test: &str, definitions: &[(&str, &str)]) -> PathBuf {
+        let assets =
+            std::env::temp_dir().join(format!("bevycraft_blocks_{test}_{}", std::process::id()));
+
+        let _ = fs::remove_dir_all(&assets);
+
+        for (path, definition) in definitions {
+            let file = assets
+                .join(AssetLocation::DEFAULT_NAMESPACE)
+                .join(BLOCKS_DIRECTORY)
+                .join(format!("{path}.{DEFINITION_EXTENSION}"));
+
+            fs::create_dir_all(file.parent().unwrap()).unwrap();
+            fs::write(file, definition).unwrap();
+        }
+
+        assets
+    }
+
+    fn registry() -> DefaultedRegistry<Block> {
+        let mut registry = Registrar::<Block>::isolated();
+
+        register_blocks(&mut registry).unwrap();
+
+        registry
+    }
+
+    #[test]
+    fn keeps_code_side_states_when_properties_are_omitted() {
+        let assets = write_definitions("omitted", &[("oak_planks_stair", "(hardness: 4.0)")]);
+        let mut registry = registry();
+
+        let applied = register_block_definitions(&mut registry, &assets).unwrap();
+
+        let stair = registry
+            .get_by_key(&AssetLocation::parse("oak_planks_stair"))
+            .unwrap();
+
+        assert_eq!(applied, 1);
+        assert_eq!(stair.hardness(), 4.0);
+        assert_eq!(
+            stair.states().properties(),
+            [FACING.key(), HALF.key(), WATERLOGGED.key()]
+        );
+    }
+
+    #[test]
+    fn rejects_properties_not_matching_the_code_side_block() {
+        let assets = write_definitions(
+            "mismatch",
+            &[("oak_planks_stair", r#"(properties: ["half", "facing"])"#)],
+        );
+
+        assert!(matches!(
+            register_block_definitions(&mut registry(), &assets),
+            Err(BlockDefinitionError::PropertyMismatch { found, .. })
+                if *found == ["half", "facing"]
+        ));
+
+        let assets = write_definitions(
+            "matching",
+            &[(
+                "oak_planks_stair",
+                r#"(properties: ["facing", "half", "waterlogged"])"#,
+            )],
+        );
+
+        assert!(register_block_definitions(&mut registry(), &assets).is_ok());
+    }
+
+    #[test]
+    fn rejects_unknown_flags() {
+        let assets = write_definitions("unknown_flag", &[("marble", r#"(flags: ["glowing"])"#)]);
+
+        assert!(matches!(
+            load_block_definitions(&assets),
+            Err(BlockDefinitionError::UnknownFlag { flag, .. }) if flag == "glowing"
+        ));
+    }
+
+    #[test]
+    fn rejects_unknown_properties() {
+        let assets = write_definitions(
+            "unknown_property",
+            &[("marble", r#"(properties: ["facing", "color"])"#)],
+        );
+
+        assert!(matches!(
+            load_block_definitions(&assets),
+            Err(BlockDefinitionError::UnknownProperty { property, .. }) if property == "color"
+        ));
+    }
+
+    #[test]
+    fn rejects_malformed_shapes() {
+        // The first box is valid, so the error points at the second.
+        for (test, max) in [
+            ("inverted", "(1.0, -0.5, 1.0)"),
+            ("outside", "(1.0, 1.5, 1.0)"),
+        ] {
+            let definition = format!(
+                "(shape: [(min: (0.0, 0.0, 0.0), max: (1.0, 0.5, 1.0)), \
+                 (min: (0.0, 0.0, 0.0), max: {max})])"
+            );
+            let assets = write_definitions(test, &[("marble", &definition)]);
+
+            assert!(matches!(
+                load_block_definitions(&assets),
+                Err(BlockDefinitionError::MalformedShape { index: 1, .. })
+            ));
+        }
+    }
+}
@@ -0,0 +1,80 @@
+use std::{
+    any::Any,
+    fmt::{Debug, Formatter},
+};
+
+/// Structured data attached to a single placed block, e.g. the contents of a container.
+///
+/// Implemented for every `Clone + Debug` type that is `Send + Sync`.
+pub trait BlockEntity: Any + Debug + Send + Sync {
+    fn clone_boxed(&self) -> Box<dyn BlockEntity>;
+}
+
+impl<T: Any + Clone + Debug + Send + Sync> BlockEntity for T {
+    #[inline]
+    fn clone_boxed(&self) -> Box<dyn BlockEntity> {
+        Box::new(self.clone())
+    }
+}
+
+impl dyn BlockEntity {
+    #[inline(always)]
+    pub fn is<T: BlockEntity>(&self) -> bool {
+        (self as &dyn Any).is::<T>()
+    }
+
+    #[inline(always)]
+    pub fn downcast_ref<T: BlockEntity>(&self) -> Option<&T> {
+        (self as &dyn Any).downcast_ref()
+    }
+
+    #[inline(always)]
+    pub fn downcast_mut<T: BlockEntity>(&mut self) -> Option<&mut T> {
+        (self as &mut dyn Any).downcast_mut()
+    }
+}
+
+/// Creates the block entity of a block whenever that block is placed.
+#[derive(Clone, Copy)]
+pub struct BlockEntityType {
+    name: &'static str,
+    create: fn() -> Box<dyn BlockEntity>,
+}
+
+impl BlockEntityType {
+    /// A block entity starting out as `T::default()`.
+    #[inline]
+    pub fn of<T: BlockEntity + Default>() -> Self {
+        Self {
+            name: std::any::type_name::<T>(),
+            create: create_default::<T>,
+        }
+    }
+
+    #[inline(always)]
+    pub fn create(&self) -> Box<dyn BlockEntity> {
+        (self.create)()
+    }
+
+    #[inline(always)]
+    pub const fn name(&self) -> &'static str {
+        self.name
+    }
+}
+
+impl PartialEq for BlockEntityType {
+    #[inline(always)]
+    fn eq(&self, other: &Self) -> bool {
+        std::ptr::fn_addr_eq(self.create, other.create)
+    }
+}
+
+impl Debug for BlockEntityType {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("BlockEntityType").field(&self.name).finish()
+    }
+}
+
+fn create_default<T: BlockEntity + Default>() -> Box<dyn BlockEntity> {
+    Box::new(T::default())
+}
@@ -6,11 +6,12 @@ bitflags::bitflags! {
         const OCCLUDABLE    = 1 << 1;
         const SEE_THROUGH   = 1 << 2;
         const REPLACEABLE   = 1 << 3;
-        const CAN_SUPPORT   = 1 << 4;
         const DOES_CONNECT  = 1 << 5;
         const DOES_SPAWN    = 1 << 6;
         const CLIMBABLE     = 1 << 7;
         const PASSABLE      = 1 << 8;
+        const FLUID         = 1 << 9;
+        const FALLS         = 1 << 10;
     }
 }
 
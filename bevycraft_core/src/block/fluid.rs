@@ -0,0 +1,210 @@
+use {
+    crate::{
+        blocks::AIR,
+        prelude::{BlockCallbacks, BlockFlags, BlockLevel, BlockStates, BlockTicker, Direction},
+        properties::{FALLING, LEVEL},
+    },
+    bevy::math::IVec3,
+};
+
+/// Strength of sources and falling fluid. Flowing fluid at level `n` has `SOURCE_STRENGTH - n`.
+const SOURCE_STRENGTH: u8 = 8;
+
+const HORIZONTAL: [Direction; 4] = [
+    Direction::PosX,
+    Direction::NegX,
+    Direction::PosZ,
+    Direction::NegZ,
+];
+
+/// A fluid spreading through scheduled ticks, like water.
+///
+/// Sources have level `0`. Fluid flows down as falling fluid and, once it cannot fall, spreads
+/// sideways one level further per block until level `7`. Two horizontal sources next to a
+/// supported block create a new source.
+#[derive(Debug)]
+pub struct FlowingFluid {
+    /// The block of this fluid.
+    pub block: fn() -> usize,
+    /// Ticks between two flow steps.
+    pub tick_delay: u32,
+}
+
+impl FlowingFluid {
+    /// State of this fluid at `level`, falling or not.
+    pub fn state(&self, level: u8, falling: bool) -> usize {
+        let states = BlockStates::global();
+        let base = states.default_state((self.block)());
+
+        states
+            .with(base, LEVEL, level)
+            .and_then(|state| states.with(state, FALLING, falling))
+            .unwrap_or(base)
+    }
+
+    /// Strength of `state` if it is this fluid.
+    pub fn strength(&self, state: usize) -> Option<u8> {
+        let states = BlockStates::global();
+
+        if states.block_of(state) != (self.block)() {
+            return None;
+        }
+
+        if states.value(state, FALLING).unwrap_or(false) {
+            return Some(SOURCE_STRENGTH);
+        }
+
+        Some(SOURCE_STRENGTH - states.value(state, LEVEL).unwrap_or(0))
+    }
+
+    #[inline]
+    pub fn is_source(&self, state: usize) -> bool {
+        let states = BlockStates::global();
+
+        states.block_of(state) == (self.block)()
+            && states.value(state, LEVEL) == Some(0)
+            && states.value(state, FALLING) != Some(true)
+    }
+
+    /// What the fluid at `position` should be given its neighbors, or `None` if it dries up.
+    fn expected_state(&self, level: &dyn BlockLevel, position: IVec3) -> Option<usize> {
+        let above = level.get_state(position + Direction::PosY.offset());
+
+        if above.is_some_and(|state| self.strength(state).is_some()) {
+            return Some(self.state(0, true));
+        }
+
+        let mut sources = 0;
+        let mut strongest = 0;
+
+        for direction in HORIZONTAL {
+            let Some(state) = level.get_state(position + direction.offset()) else {
+                continue;
+            };
+
+            if self.is_source(state) {
+                sources += 1;
+            }
+
+            strongest = strongest.max(self.strength(state).unwrap_or(0));
+        }
+
+        if sources >= 2 {
+            let below = level.get_state(position + Direction::NegY.offset());
+
+            if below.is_some_and(|state| {
+                self.is_source(state) || BlockStates::global().can_support(state)
+            }) {
+                return Some(self.state(0, false));
+            }
+        }
+
+        if strongest <= 1 {
+            return None;
+        }
+
+        Some(self.state(SOURCE_STRENGTH - (strongest - 1), false))
+    }
+
+    /// Whether fluid of `strength` may replace the block at `position`.
+    fn can_flow_into(&self, level: &dyn BlockLevel, position: IVec3, strength: u8) -> bool {
+        let Some(state) = level.get_state(position) else {
+            return false;
+        };
+
+        if let Some(existing) = self.strength(state) {
+            return !self.is_source(state) && existing < strength;
+        }
+
+        let flags = BlockStates::global().flags_of(BlockStates::global().block_of(state));
+
+        flags.contains(BlockFlags::AIR)
+            || (flags.contains(BlockFlags::REPLACEABLE) && !flags.contains(BlockFlags::FLUID))
+    }
+
+    fn flow_into(&self, level: &mut dyn BlockLevel, position: IVec3, state: usize) {
+        if level.set_state(position, state) {
+            level.schedule_tick(position, self.tick_delay);
+            level.notify_neighbors(position);
+        }
+    }
+}
+
+impl BlockTicker for FlowingFluid {
+    fn scheduled_tick(&self, level: &mut dyn BlockLevel, position: IVec3, state: usize) {
+        if !self.is_source(state) {
+            match self.expected_state(level, position) {
+                None => {
+                    level.set_state(position, AIR.default_state());
+                    level.notify_neighbors(position);
+
+                    return;
+                }
+                Some(expected) if expected != state => {
+                    level.set_state(position, expected);
+                    level.schedule_tick(position, self.tick_delay);
+                    level.notify_neighbors(position);
+
+                    return;
+                }
+                Some(_) => {}
+            }
+        }
+
+        let below = position + Direction::NegY.offset();
+
+        if self.can_flow_into(level, below, SOURCE_STRENGTH) {
+            self.flow_into(level, below, self.state(0, true));
+
+            if !self.is_source(state) {
+                return;
+            }
+        }
+
+        let Some(strength) = self.strength(state) else {
+            return;
+        };
+
+        if strength <= 1 {
+            return;
+        }
+
+        let spread = self.state(SOURCE_STRENGTH - (strength - 1), false);
+
+        for direction in HORIZONTAL {
+            let target = position + direction.offset();
+
+            if self.can_flow_into(level, target, strength - 1) {
+                self.flow_into(level, target, spread);
+            }
+        }
+    }
+}
+
+impl BlockCallbacks for FlowingFluid {
+    fn on_place(&self, level: &mut dyn BlockLevel, position: IVec3, _: usize) {
+        level.schedule_tick(position, self.tick_delay);
+    }
+
+    fn on_neighbor_changed(&self, level: &mut dyn BlockLevel, position: IVec3, _: usize, _: IVec3) {
+        level.schedule_tick(position, self.tick_delay);
+    }
+}
+
+/// Height of the surface of `state` within its block, from `0.0` to `1.0`, or `None` if it is
+/// not a fluid.
+pub fn fluid_height(state: usize) -> Option<f32> {
+    let states = BlockStates::global();
+
+    if !states.flags_of(states.block_of(state)).contains(BlockFlags::FLUID) {
+        return None;
+    }
+
+    if states.value(state, FALLING).unwrap_or(false) {
+        return Some(1.0);
+    }
+
+    let level = states.value(state, LEVEL).unwrap_or(0);
+
+    Some((SOURCE_STRENGTH - level) as f32 / (SOURCE_STRENGTH + 1) as f32)
+}
@@ -0,0 +1,32 @@
+use bevy::math::IVec3;
+
+/// World access handed to block callbacks. Positions are world block positions.
+pub trait BlockLevel {
+    /// State at `position`, or `None` if its chunk is not loaded.
+    fn get_state(&self, position: IVec3) -> Option<usize>;
+
+    /// Replaces the state at `position` without running any callbacks, returning `false` if
+    /// its chunk is not loaded.
+    fn set_state(&mut self, position: IVec3, state: usize) -> bool;
+
+    /// Schedules a tick of the block at `position` in `delay` ticks, at least one.
+    fn schedule_tick(&mut self, position: IVec3, delay: u32);
+
+    /// A random number in `0..bound`.
+    fn random(&mut self, bound: u32) -> u32;
+
+    /// Places `state` at `position`, then runs its `on_place` callback and notifies the
+    /// neighbors. Returns `false` if the chunk is not loaded.
+    fn place_block(&mut self, position: IVec3, state: usize) -> bool;
+
+    /// Runs the `on_break` callback of the block at `position`, replaces it with air and
+    /// notifies the neighbors. Returns the broken state, or `None` if the chunk is not loaded or
+    /// there is only air to break.
+    fn break_block(&mut self, position: IVec3) -> Option<usize>;
+
+    /// Runs the `on_use` callback of the block at `position`, returning whether it was handled.
+    fn use_block(&mut self, position: IVec3) -> bool;
+
+    /// Runs the `on_neighbor_changed` callbacks of the 6 blocks adjacent to `position`.
+    fn notify_neighbors(&mut self, position: IVec3);
+}
@@ -1,5 +1,15 @@
 pub mod behaviour;
 pub mod block;
+pub mod callbacks;
 pub mod blocks;
+pub mod definition;
+pub mod entity;
 pub mod flags;
+pub mod fluid;
+pub mod level;
+pub mod properties;
 pub mod shape;
+pub mod signal;
+pub mod state;
+pub mod tags;
+pub mod ticker;
@@ -0,0 +1,94 @@
+use crate::block::{
+    shape::BlockRotation,
+    state::{Property, PropertyKey, StateEnum},
+};
+
+pub const FACING: Property<Facing> = Property::enumeration("facing");
+
+pub const HALF: Property<Half> = Property::enumeration("half");
+
+pub const AXIS: Property<Axis> = Property::enumeration("axis");
+
+pub const WATERLOGGED: Property<bool> = Property::boolean("waterlogged");
+
+pub const OPEN: Property<bool> = Property::boolean("open");
+
+/// Distance of flowing fluid from its source, `0` being the source itself.
+pub const LEVEL: Property<u8> = Property::int("level", 0, 7);
+
+pub const FALLING: Property<bool> = Property::boolean("falling");
+
+/// Signal strength carried by a conductor.
+pub const POWER: Property<u8> = Property::int("power", 0, 15);
+
+/// Whether a consumer currently receives a signal.
+pub const POWERED: Property<bool> = Property::boolean("powered");
+
+/// Every built-in property, used to resolve properties referenced by name.
+pub const ALL_PROPERTIES: &[PropertyKey] = &[
+    FACING.key(),
+    HALF.key(),
+    AXIS.key(),
+    WATERLOGGED.key(),
+    OPEN.key(),
+    LEVEL.key(),
+    FALLING.key(),
+    POWER.key(),
+    POWERED.key(),
+];
+
+macro_rules! state_enum {
+    ($name:ident { $($variant:ident => $key:literal),+ $(,)? }) => {
+        #[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+        pub enum $name {
+            $($variant),+
+        }
+
+        impl StateEnum for $name {
+            const NAMES: &'static [&'static str] = &[$($key),+];
+
+            #[inline(always)]
+            fn index(self) -> usize {
+                self as usize
+            }
+
+            #[inline]
+            fn from_index(index: usize) -> Option<Self> {
+                const VARIANTS: &[$name] = &[$($name::$variant),+];
+
+                VARIANTS.get(index).copied()
+            }
+        }
+    };
+}
+
+state_enum!(Facing {
+    North => "north",
+    South => "south",
+    East => "east",
+    West => "west",
+});
+
+state_enum!(Half {
+    Bottom => "bottom",
+    Top => "top",
+});
+
+state_enum!(Axis {
+    Y => "y",
+    X => "x",
+    Z => "z",
+});
+
+impl From<Facing> for BlockRotation {
+    /// Rotation turning a shape authored facing north towards `facing`.
+    #[inline]
+    fn from(facing: Facing) -> Self {
+        match facing {
+            Facing::North => Self::None,
+            Facing::East => Self::Clockwise90,
+            Facing::South => Self::Clockwise180,
+            Facing::West => Self::CounterClockwise90,
+        }
+    }
+}
@@ -1,6 +1,10 @@
-use bevy::math::{
-    Mat3, Quat, Vec3A,
-    bounding::{Aabb3d, BoundingVolume, IntersectsVolume},
+use {
+    crate::{block::properties::Axis, direction::Direction},
+    bevy::math::{
+        Mat3, Quat, Vec3A,
+        bounding::{Aabb3d, BoundingVolume, IntersectsVolume},
+    },
+    std::ops::Range,
 };
 
 #[derive(Debug, Clone, PartialEq)]
@@ -74,6 +78,268 @@ impl<'a> From<&'a [Aabb3d]> for BlockShape {
     }
 }
 
+/// A clockwise rotation around the vertical axis of the block, seen from above.
+///
+/// Shapes are authored facing north (`-Z`), so rotating by [`BlockRotation::Clockwise90`]
+/// makes them face east (`+X`).
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq, Hash)]
+pub enum BlockRotation {
+    #[default]
+    None,
+    Clockwise90,
+    Clockwise180,
+    CounterClockwise90,
+}
+
+impl BlockRotation {
+    /// Rotates a point of the unit block around its center.
+    #[inline]
+    pub fn apply(self, point: Vec3A) -> Vec3A {
+        match self {
+            Self::None => point,
+            Self::Clockwise90 => Vec3A::new(1.0 - point.z, point.y, point.x),
+            Self::Clockwise180 => Vec3A::new(1.0 - point.x, point.y, 1.0 - point.z),
+            Self::CounterClockwise90 => Vec3A::new(point.z, point.y, 1.0 - point.x),
+        }
+    }
+}
+
+/// A reflection of the block across one of its vertical center planes.
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq, Hash)]
+pub enum BlockMirror {
+    #[default]
+    None,
+    /// Swaps west and east (`x -> 1 - x`).
+    LeftRight,
+    /// Swaps north and south (`z -> 1 - z`).
+    FrontBack,
+}
+
+impl BlockMirror {
+    /// Mirrors a point of the unit block across its center.
+    #[inline]
+    pub fn apply(self, point: Vec3A) -> Vec3A {
+        match self {
+            Self::None => point,
+            Self::LeftRight => Vec3A::new(1.0 - point.x, point.y, point.z),
+            Self::FrontBack => Vec3A::new(point.x, point.y, 1.0 - point.z),
+        }
+    }
+}
+
+/// Where a ray or a swept box first touches a [`BlockShape`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ShapeHit {
+    /// Distance along the ray direction for [`BlockShape::cast_ray`], or fraction of the motion
+    /// for [`BlockShape::sweep_aabb`].
+    pub time: f32,
+    /// Outward normal of the face that was hit, or of the nearest face if the query started
+    /// inside the shape.
+    pub normal: Vec3A,
+}
+
+impl BlockShape {
+    #[inline]
+    pub fn bboxes(&self) -> &[Aabb3d] {
+        match self {
+            Self::None => &[],
+            Self::Single(bbox) => std::slice::from_ref(bbox),
+            Self::Multi { bboxes, .. } => bboxes,
+        }
+    }
+
+    /// Rotates the shape by a multiple of 90° around the block center. Unlike
+    /// [`BoundingVolume::rotate_by`], the result is exact and every box stays axis-aligned.
+    #[inline]
+    pub fn rotated(&self, rotation: BlockRotation) -> Self {
+        match rotation {
+            BlockRotation::None => self.clone(),
+            _ => self.map_points(|p| rotation.apply(p)),
+        }
+    }
+
+    #[inline]
+    pub fn mirrored(&self, mirror: BlockMirror) -> Self {
+        match mirror {
+            BlockMirror::None => self.clone(),
+            _ => self.map_points(|p| mirror.apply(p)),
+        }
+    }
+
+    /// Flips the shape upside down, e.g. to turn a bottom slab into a top slab.
+    #[inline]
+    pub fn flipped_vertically(&self) -> Self {
+        self.map_points(|p| Vec3A::new(p.x, 1.0 - p.y, p.z))
+    }
+
+    /// Turns a shape authored along the `Y` axis (e.g. a log) so it runs along `axis`.
+    #[inline]
+    pub fn aligned_to(&self, axis: Axis) -> Self {
+        match axis {
+            Axis::Y => self.clone(),
+            Axis::X => self.map_points(|p| Vec3A::new(p.y, 1.0 - p.x, p.z)),
+            Axis::Z => self.map_points(|p| Vec3A::new(p.x, 1.0 - p.z, p.y)),
+        }
+    }
+
+    /// Maps the corners of every box through `f`, which must send axis-aligned boxes to
+    /// axis-aligned boxes.
+    fn map_points(&self, f: impl Fn(Vec3A) -> Vec3A) -> Self {
+        let map = |bbox: &Aabb3d| {
+            let (a, b) = (f(bbox.min), f(bbox.max));
+
+            Aabb3d {
+                min: a.min(b),
+                max: a.max(b),
+            }
+        };
+
+        match self {
+            Self::None => Self::None,
+            Self::Single(bbox) => Self::Single(map(bbox)),
+            Self::Multi { bboxes, .. } => {
+                let bboxes: Box<[Aabb3d]> = bboxes.iter().map(map).collect();
+
+                Self::Multi {
+                    broad_bounds: compute_bounds(&bboxes),
+                    bboxes,
+                }
+            }
+        }
+    }
+
+    /// Space covered by either shape.
+    #[inline]
+    pub fn union(&self, other: &Self) -> Self {
+        self.combine(other, |a, b| a || b)
+    }
+
+    /// Space covered by both shapes.
+    #[inline]
+    pub fn intersection(&self, other: &Self) -> Self {
+        self.combine(other, |a, b| a && b)
+    }
+
+    /// Space covered by `self` but not by `other`.
+    #[inline]
+    pub fn difference(&self, other: &Self) -> Self {
+        self.combine(other, |a, b| a && !b)
+    }
+
+    /// Rebuilds the shape from as few boxes as the greedy merge finds, dropping overlaps and
+    /// joining adjacent boxes.
+    #[inline]
+    pub fn simplified(&self) -> Self {
+        self.combine(&Self::None, |a, _| a)
+    }
+
+    /// Whether the boxes flush with the face of the unit block towards `direction` cover it
+    /// entirely.
+    #[inline]
+    pub fn covers_face(&self, direction: Direction) -> bool {
+        self.face_coverage(direction) >= 1.0 - f32::EPSILON
+    }
+
+    /// Fraction of the face of the unit block towards `direction` covered by boxes flush with it.
+    pub fn face_coverage(&self, direction: Direction) -> f32 {
+        let (axis, positive) = face_axis(direction);
+        let (u, v) = ((axis + 1) % 3, (axis + 2) % 3);
+
+        let rects: Vec<[f32; 4]> = self
+            .bboxes()
+            .iter()
+            .filter(|bbox| {
+                if positive {
+                    bbox.max[axis] >= 1.0
+                } else {
+                    bbox.min[axis] <= 0.0
+                }
+            })
+            .map(|bbox| {
+                [
+                    bbox.min[u].max(0.0),
+                    bbox.min[v].max(0.0),
+                    bbox.max[u].min(1.0),
+                    bbox.max[v].min(1.0),
+                ]
+            })
+            .filter(|[u0, v0, u1, v1]| u0 < u1 && v0 < v1)
+            .collect();
+
+        let us = coordinates(rects.iter().flat_map(|r| [r[0], r[2]]).chain([0.0, 1.0]));
+        let vs = coordinates(rects.iter().flat_map(|r| [r[1], r[3]]).chain([0.0, 1.0]));
+
+        let mut area = 0.0;
+
+        for (u0, u1) in us.iter().zip(&us[1..]) {
+            for (v0, v1) in vs.iter().zip(&vs[1..]) {
+                let (cu, cv) = ((u0 + u1) * 0.5, (v0 + v1) * 0.5);
+
+                if rects
+                    .iter()
+                    .any(|r| r[0] <= cu && cu <= r[2] && r[1] <= cv && cv <= r[3])
+                {
+                    area += (u1 - u0) * (v1 - v0);
+                }
+            }
+        }
+
+        area
+    }
+
+    /// Evaluates `op` on the cells of a grid spanned by the box edges of both shapes and
+    /// rebuilds boxes from the filled cells.
+    fn combine(&self, other: &Self, op: impl Fn(bool, bool) -> bool) -> Self {
+        if self.bboxes().is_empty() && other.bboxes().is_empty() {
+            return Self::None;
+        }
+
+        let boxes = || self.bboxes().iter().chain(other.bboxes());
+
+        let axes = [0, 1, 2].map(|axis| {
+            coordinates(boxes().flat_map(|bbox| [bbox.min[axis], bbox.max[axis]]))
+        });
+
+        let a = VoxelGrid::rasterize(&axes, self.bboxes());
+        let b = VoxelGrid::rasterize(&axes, other.bboxes());
+
+        let cells = a.cells.iter().zip(&b.cells).map(|(&a, &b)| op(a, b)).collect();
+
+        VoxelGrid { axes, cells }.into_boxes().into_boxed_slice().into()
+    }
+
+    /// Casts a ray against every box of the shape, returning the closest hit with
+    /// `time <= max_t`, measured in units of `direction`.
+    pub fn cast_ray(&self, origin: Vec3A, direction: Vec3A, max_t: f32) -> Option<ShapeHit> {
+        if let Self::Multi { broad_bounds, .. } = self {
+            ray_box(broad_bounds, origin, direction, max_t)?;
+        }
+
+        self.bboxes()
+            .iter()
+            .filter_map(|bbox| ray_box(bbox, origin, direction, max_t))
+            .min_by(|a, b| a.time.total_cmp(&b.time))
+    }
+
+    /// Sweeps `aabb` along `motion`, returning the fraction of `motion` in `[0, 1]` it can travel
+    /// before touching the shape. Boxes that only touch the shape, or start inside it and do not
+    /// move deeper past its nearest face, are not blocked.
+    pub fn sweep_aabb(&self, aabb: &Aabb3d, motion: Vec3A) -> Option<ShapeHit> {
+        let origin = aabb.center();
+        let half_size = aabb.half_size();
+
+        if let Self::Multi { broad_bounds, .. } = self {
+            ray_box(&broad_bounds.grow(half_size), origin, motion, 1.0)?;
+        }
+
+        self.bboxes()
+            .iter()
+            .filter_map(|bbox| ray_box(&bbox.grow(half_size), origin, motion, 1.0))
+            .filter(|hit| hit.time > 0.0 || hit.normal.dot(motion) < 0.0)
+            .min_by(|a, b| a.time.total_cmp(&b.time))
+    }
+}
+
 impl BoundingVolume for BlockShape {
     type Translation = Vec3A;
 
@@ -359,6 +625,190 @@ impl IntersectsVolume<BlockShape> for Aabb3d {
     }
 }
 
+/// Filled cells of a grid whose cell boundaries are given per axis.
+struct VoxelGrid {
+    axes: [Vec<f32>; 3],
+    cells: Vec<bool>,
+}
+
+impl VoxelGrid {
+    fn rasterize(axes: &[Vec<f32>; 3], bboxes: &[Aabb3d]) -> Self {
+        let [xs, ys, zs] = axes;
+        let mut cells = Vec::with_capacity(cell_count(axes));
+
+        for (x0, x1) in xs.iter().zip(&xs[1..]) {
+            for (y0, y1) in ys.iter().zip(&ys[1..]) {
+                for (z0, z1) in zs.iter().zip(&zs[1..]) {
+                    let center = Vec3A::new((x0 + x1) * 0.5, (y0 + y1) * 0.5, (z0 + z1) * 0.5);
+
+                    let filled = bboxes.iter().any(|bbox| {
+                        bbox.min.cmple(center).all() && center.cmple(bbox.max).all()
+                    });
+
+                    cells.push(filled);
+                }
+            }
+        }
+
+        Self {
+            axes: axes.clone(),
+            cells,
+        }
+    }
+
+    /// Greedily grows each unvisited filled cell along `z`, then `y`, then `x`.
+    fn into_boxes(mut self) -> Vec<Aabb3d> {
+        let [nx, ny, nz] = self.axes.each_ref().map(|axis| axis.len().saturating_sub(1));
+        let index = |x: usize, y: usize, z: usize| (x * ny + y) * nz + z;
+        let filled = |cells: &[bool], mut xs: Range<usize>, ys: Range<usize>, zs: Range<usize>| {
+            xs.all(|x| ys.clone().all(|y| zs.clone().all(|z| cells[index(x, y, z)])))
+        };
+
+        let mut bboxes = Vec::new();
+
+        for x in 0..nx {
+            for y in 0..ny {
+                for z in 0..nz {
+                    if !self.cells[index(x, y, z)] {
+                        continue;
+                    }
+
+                    let mut z1 = z + 1;
+                    while z1 < nz && filled(&self.cells, x..x + 1, y..y + 1, z1..z1 + 1) {
+                        z1 += 1;
+                    }
+
+                    let mut y1 = y + 1;
+                    while y1 < ny && filled(&self.cells, x..x + 1, y1..y1 + 1, z..z1) {
+                        y1 += 1;
+                    }
+
+                    let mut x1 = x + 1;
+                    while x1 < nx && filled(&self.cells, x1..x1 + 1, y..y1, z..z1) {
+                        x1 += 1;
+                    }
+
+                    for cx in x..x1 {
+                        for cy in y..y1 {
+                            for cz in z..z1 {
+                                self.cells[index(cx, cy, cz)] = false;
+                            }
+                        }
+                    }
+
+                    let [xs, ys, zs] = &self.axes;
+
+                    bboxes.push(Aabb3d {
+                        min: Vec3A::new(xs[x], ys[y], zs[z]),
+                        max: Vec3A::new(xs[x1], ys[y1], zs[z1]),
+                    });
+                }
+            }
+        }
+
+        bboxes
+    }
+}
+
+#[inline]
+fn cell_count(axes: &[Vec<f32>; 3]) -> usize {
+    axes.iter()
+        .map(|axis| axis.len().saturating_sub(1))
+        .product()
+}
+
+/// Sorted, deduplicated cell boundaries.
+#[inline]
+fn coordinates(values: impl Iterator<Item = f32>) -> Vec<f32> {
+    let mut values: Vec<f32> = values.collect();
+
+    values.sort_by(f32::total_cmp);
+    values.dedup();
+
+    values
+}
+
+#[inline(always)]
+const fn face_axis(direction: Direction) -> (usize, bool) {
+    match direction {
+        Direction::PosX => (0, true),
+        Direction::NegX => (0, false),
+        Direction::PosY => (1, true),
+        Direction::NegY => (1, false),
+        Direction::PosZ => (2, true),
+        Direction::NegZ => (2, false),
+    }
+}
+
+/// Slab test of a ray against a single box. Rays running along a face, or leaving a face
+/// they start on, do not hit. Rays starting inside hit at time zero on the nearest face.
+fn ray_box(bbox: &Aabb3d, origin: Vec3A, direction: Vec3A, max_t: f32) -> Option<ShapeHit> {
+    let mut near = f32::NEG_INFINITY;
+    let mut far = f32::INFINITY;
+    let mut normal = Vec3A::ZERO;
+
+    for axis in 0..3 {
+        let (o, d) = (origin[axis], direction[axis]);
+        let (min, max) = (bbox.min[axis], bbox.max[axis]);
+
+        if d == 0.0 {
+            if o <= min || o >= max {
+                return None;
+            }
+
+            continue;
+        }
+
+        let t1 = (min - o) / d;
+        let t2 = (max - o) / d;
+        let (entry, exit) = if t1 < t2 { (t1, t2) } else { (t2, t1) };
+
+        if entry > near {
+            near = entry;
+            normal = Vec3A::ZERO;
+            normal[axis] = -d.signum();
+        }
+
+        far = far.min(exit);
+    }
+
+    if near > far || far <= 0.0 || near > max_t {
+        return None;
+    }
+
+    if near < 0.0 {
+        return Some(ShapeHit {
+            time: 0.0,
+            normal: nearest_face_normal(bbox, origin),
+        });
+    }
+
+    Some(ShapeHit { time: near, normal })
+}
+
+/// Outward normal of the face of `bbox` closest to `point`.
+fn nearest_face_normal(bbox: &Aabb3d, point: Vec3A) -> Vec3A {
+    let mut depth = f32::INFINITY;
+    let mut normal = Vec3A::ZERO;
+
+    for axis in 0..3 {
+        let faces = [
+            (point[axis] - bbox.min[axis], -1.0),
+            (bbox.max[axis] - point[axis], 1.0),
+        ];
+
+        for (distance, sign) in faces {
+            if distance < depth {
+                depth = distance;
+                normal = Vec3A::ZERO;
+                normal[axis] = sign;
+            }
+        }
+    }
+
+    normal
+}
+
 #[inline(always)]
 fn compute_bounds(bboxes: &[Aabb3d]) -> Aabb3d {
     let mut min = bboxes[0].min;
@@ -371,3 +821,166 @@ fn compute_bounds(bboxes: &[Aabb3d]) -> Aabb3d {
 
     Aabb3d { min, max }
 }
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::consts::{FULL_SHAPE, SLAB_SHAPE, STAIR_SHAPE},
+    };
+
+    fn cube(min: [f32; 3], max: [f32; 3]) -> Aabb3d {
+        Aabb3d {
+            min: Vec3A::from_array(min),
+            max: Vec3A::from_array(max),
+        }
+    }
+
+    #[test]
+    fn ray_hits_the_nearest_face() {
+        let slab = BlockShape::from(SLAB_SHAPE);
+        let down = Vec3A::NEG_Y;
+
+        let hit = slab
+            .cast_ray(Vec3A::new(0.5, 2.0, 0.5), down, 10.0)
+            .unwrap();
+
+        assert_eq!(hit.time, 1.5);
+        assert_eq!(hit.normal, Vec3A::Y);
+        assert_eq!(slab.cast_ray(Vec3A::new(0.5, 2.0, 0.5), down, 1.0), None);
+        assert_eq!(slab.cast_ray(Vec3A::new(2.0, 2.0, 0.5), down, 10.0), None);
+    }
+
+    #[test]
+    fn ray_picks_the_closest_box() {
+        let stair = BlockShape::from(STAIR_SHAPE);
+
+        let hit = stair
+            .cast_ray(Vec3A::new(0.5, 0.75, 2.0), Vec3A::NEG_Z, 10.0)
+            .unwrap();
+
+        assert_eq!(hit.time, 1.5);
+        assert_eq!(hit.normal, Vec3A::Z);
+    }
+
+    #[test]
+    fn ray_starting_inside_reports_the_nearest_face() {
+        let full = BlockShape::from(FULL_SHAPE);
+
+        let hit = full
+            .cast_ray(Vec3A::new(0.5, 0.9, 0.5), Vec3A::X, 10.0)
+            .unwrap();
+
+        assert_eq!(hit.time, 0.0);
+        assert_eq!(hit.normal, Vec3A::Y);
+    }
+
+    #[test]
+    fn sweep_stops_on_the_shape() {
+        let full = BlockShape::from(FULL_SHAPE);
+        let falling = cube([0.25, 1.5, 0.25], [0.75, 2.0, 0.75]);
+
+        let hit = full.sweep_aabb(&falling, Vec3A::NEG_Y).unwrap();
+
+        assert_eq!(hit.time, 0.5);
+        assert_eq!(hit.normal, Vec3A::Y);
+    }
+
+    #[test]
+    fn sweep_slides_along_touching_faces() {
+        let full = BlockShape::from(FULL_SHAPE);
+        let resting = cube([0.25, 1.0, 0.25], [0.75, 1.5, 0.75]);
+
+        assert_eq!(full.sweep_aabb(&resting, Vec3A::X), None);
+    }
+
+    #[test]
+    fn sweep_lets_overlapping_boxes_leave() {
+        let full = BlockShape::from(FULL_SHAPE);
+        let stuck = cube([0.25, 0.65, 0.25], [0.75, 1.15, 0.75]);
+
+        assert_eq!(full.sweep_aabb(&stuck, Vec3A::Y), None);
+        assert_eq!(full.sweep_aabb(&stuck, Vec3A::NEG_Y).unwrap().time, 0.0);
+    }
+
+    #[test]
+    fn rotation_turns_the_shape_clockwise() {
+        let stair = BlockShape::from(STAIR_SHAPE);
+        let east = stair.rotated(BlockRotation::Clockwise90);
+
+        assert_eq!(east.bboxes()[1], cube([0.5, 0.5, 0.0], [1.0, 1.0, 1.0]));
+        assert!(east.covers_face(Direction::PosX));
+        assert!(!east.covers_face(Direction::NegZ));
+        assert_eq!(
+            stair.rotated(BlockRotation::Clockwise180),
+            east.rotated(BlockRotation::Clockwise90)
+        );
+        assert_eq!(east.rotated(BlockRotation::CounterClockwise90), stair);
+    }
+
+    #[test]
+    fn mirroring_and_flipping_move_boxes_across_the_center() {
+        let stair = BlockShape::from(STAIR_SHAPE);
+        let slab = BlockShape::from(SLAB_SHAPE);
+
+        assert!(stair
+            .mirrored(BlockMirror::FrontBack)
+            .covers_face(Direction::PosZ));
+        assert_eq!(stair.mirrored(BlockMirror::LeftRight), stair);
+        assert_eq!(
+            slab.flipped_vertically(),
+            BlockShape::Single(cube([0.0, 0.5, 0.0], [1.0, 1.0, 1.0]))
+        );
+    }
+
+    #[test]
+    fn alignment_lays_vertical_shapes_down() {
+        let pillar = BlockShape::Single(cube([0.25, 0.0, 0.25], [0.75, 1.0, 0.75]));
+
+        assert_eq!(
+            pillar.aligned_to(Axis::X),
+            BlockShape::Single(cube([0.0, 0.25, 0.25], [1.0, 0.75, 0.75]))
+        );
+        assert_eq!(
+            pillar.aligned_to(Axis::Z),
+            BlockShape::Single(cube([0.25, 0.25, 0.0], [0.75, 0.75, 1.0]))
+        );
+    }
+
+    #[test]
+    fn boolean_operations_merge_into_few_boxes() {
+        let full = BlockShape::from(FULL_SHAPE);
+        let bottom = BlockShape::from(SLAB_SHAPE);
+        let top = bottom.flipped_vertically();
+
+        assert_eq!(bottom.union(&top), full);
+        assert_eq!(full.difference(&bottom), top);
+        assert_eq!(bottom.intersection(&top), BlockShape::None);
+        assert_eq!(
+            BlockShape::from(STAIR_SHAPE).intersection(&top),
+            BlockShape::Single(STAIR_SHAPE[1])
+        );
+    }
+
+    #[test]
+    fn simplifying_drops_overlaps() {
+        let overlapping = BlockShape::from([
+            cube([0.0, 0.0, 0.0], [1.0, 0.75, 1.0]),
+            cube([0.0, 0.25, 0.0], [1.0, 1.0, 1.0]),
+        ]);
+
+        assert_eq!(overlapping.simplified(), BlockShape::from(FULL_SHAPE));
+    }
+
+    #[test]
+    fn face_coverage_only_counts_flush_boxes() {
+        let stair = BlockShape::from(STAIR_SHAPE);
+
+        assert_eq!(stair.face_coverage(Direction::PosY), 0.5);
+        assert_eq!(stair.face_coverage(Direction::PosZ), 0.5);
+        assert_eq!(stair.face_coverage(Direction::NegY), 1.0);
+        assert!(stair.covers_face(Direction::NegZ));
+        assert!(!stair.covers_face(Direction::PosX));
+        assert_eq!(BlockShape::None.face_coverage(Direction::NegY), 0.0);
+    }
+}
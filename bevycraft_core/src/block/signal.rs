@@ -0,0 +1,136 @@
+use {
+    crate::{
+        prelude::{
+            BlockCallbacks, BlockFlags, BlockLevel, BlockStates, BlockTicker, Direction, OpenOnUse,
+        },
+        properties::{OPEN, POWER, POWERED},
+    },
+    bevy::math::IVec3,
+};
+
+/// Ticks between a conductor noticing a change around it and updating its power.
+pub const SIGNAL_DELAY: u32 = 1;
+
+/// Signal the block at `position` sends into its neighbors: the emission of a source, or the
+/// power of a conductor minus one. Only blocks flagged [`BlockFlags::DOES_CONNECT`] send signals.
+pub fn signal_at(level: &dyn BlockLevel, position: IVec3) -> u8 {
+    let states = BlockStates::global();
+
+    let Some(state) = level.get_state(position) else {
+        return 0;
+    };
+
+    if !states
+        .flags_of(states.block_of(state))
+        .contains(BlockFlags::DOES_CONNECT)
+    {
+        return 0;
+    }
+
+    let carried = states
+        .value(state, POWER)
+        .map_or(0, |power| power.saturating_sub(1));
+
+    states.signal_emission(state).max(carried)
+}
+
+/// Strongest signal the block at `position` receives from its 6 neighbors.
+pub fn received_signal(level: &dyn BlockLevel, position: IVec3) -> u8 {
+    Direction::ALL
+        .iter()
+        .map(|direction| signal_at(level, position + direction.offset()))
+        .max()
+        .unwrap_or(0)
+}
+
+/// Carries signals between connecting blocks in its `power` property, losing one level per
+/// block.
+///
+/// Changes around the conductor schedule a tick instead of updating it right away, so a change
+/// spreads one block per tick and each conductor updates at most once per tick. Since every
+/// block weakens the signal, a loop of conductors cut from its source powers itself down to `0`
+/// instead of keeping itself on.
+#[derive(Debug)]
+pub struct SignalConductor;
+
+impl BlockCallbacks for SignalConductor {
+    fn on_place(&self, level: &mut dyn BlockLevel, position: IVec3, _: usize) {
+        level.schedule_tick(position, SIGNAL_DELAY);
+    }
+
+    fn on_neighbor_changed(
+        &self,
+        level: &mut dyn BlockLevel,
+        position: IVec3,
+        _: usize,
+        _: IVec3,
+    ) {
+        level.schedule_tick(position, SIGNAL_DELAY);
+    }
+}
+
+impl BlockTicker for SignalConductor {
+    fn scheduled_tick(&self, level: &mut dyn BlockLevel, position: IVec3, state: usize) {
+        let states = BlockStates::global();
+        let power = received_signal(level, position);
+
+        if states.value(state, POWER) == Some(power) {
+            return;
+        }
+
+        if let Some(powered) = states.with(state, POWER, power) {
+            level.set_state(position, powered);
+            level.notify_neighbors(position);
+        }
+    }
+}
+
+/// Opens the block while it receives a signal and closes it once the signal is gone, e.g. for
+/// doors and trapdoors. It can still be toggled by hand like [`OpenOnUse`].
+///
+/// The `powered` property remembers the last signal, so the block only follows changes of the
+/// signal and keeps a state set by hand until then.
+#[derive(Debug)]
+pub struct OpenOnSignal;
+
+impl OpenOnSignal {
+    /// Opens or closes the block if the signal it receives changed.
+    fn follow_signal(&self, level: &mut dyn BlockLevel, position: IVec3, state: usize) {
+        let states = BlockStates::global();
+        let powered = received_signal(level, position) > 0;
+
+        if states.value(state, POWERED) != Some(!powered) {
+            return;
+        }
+
+        let Some(next) = states
+            .with(state, POWERED, powered)
+            .and_then(|state| states.with(state, OPEN, powered))
+        else {
+            return;
+        };
+
+        level.set_state(position, next);
+        level.notify_neighbors(position);
+    }
+}
+
+impl BlockCallbacks for OpenOnSignal {
+    fn on_place(&self, level: &mut dyn BlockLevel, position: IVec3, state: usize) {
+        self.follow_signal(level, position, state);
+    }
+
+    fn on_neighbor_changed(
+        &self,
+        level: &mut dyn BlockLevel,
+        position: IVec3,
+        state: usize,
+        _: IVec3,
+    ) {
+        self.follow_signal(level, position, state);
+    }
+
+    fn on_use(&self, level: &mut dyn BlockLevel, position: IVec3, state: usize) -> bool {
+        OpenOnUse.on_use(level, position, state)
+    }
+}
@@ -0,0 +1,843 @@
+use {
+    crate::{
+        prelude::*,
+        properties::{AXIS, Axis, FACING, Facing, HALF, Half, OPEN},
+    },
+    serde::{Deserialize, Serialize},
+    std::{marker::PhantomData, ops::Range, sync::OnceLock},
+};
+
+static BLOCK_STATES: OnceLock<BlockStates> = OnceLock::new();
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PropertyKind {
+    Bool,
+    Int { min: u8, max: u8 },
+    Enum(&'static [&'static str]),
+}
+
+impl PropertyKind {
+    #[inline]
+    pub const fn cardinality(&self) -> usize {
+        match self {
+            Self::Bool => 2,
+            Self::Int { min, max } => (*max - *min) as usize + 1,
+            Self::Enum(names) => names.len(),
+        }
+    }
+
+    #[inline]
+    pub fn index_of(&self, value: &str) -> Option<usize> {
+        match self {
+            Self::Bool => match value {
+                "false" => Some(0),
+                "true" => Some(1),
+                _ => None,
+            },
+            Self::Int { min, max } => value
+                .parse::<u8>()
+                .ok()
+                .filter(|v| (*min..=*max).contains(v))
+                .map(|v| (v - *min) as usize),
+            Self::Enum(names) => names.iter().position(|name| *name == value),
+        }
+    }
+
+    #[inline]
+    pub fn value_name(&self, index: usize) -> Option<Box<str>> {
+        if index >= self.cardinality() {
+            return None;
+        }
+
+        match self {
+            Self::Bool => Some(Box::from(if index == 0 { "false" } else { "true" })),
+            Self::Int { min, .. } => Some((*min as usize + index).to_string().into_boxed_str()),
+            Self::Enum(names) => Some(Box::from(names[index])),
+        }
+    }
+}
+
+/// Type-erased description of a block state property.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct PropertyKey {
+    name: &'static str,
+    kind: PropertyKind,
+}
+
+impl PropertyKey {
+    #[inline(always)]
+    pub const fn name(&self) -> &'static str {
+        self.name
+    }
+
+    #[inline(always)]
+    pub const fn kind(&self) -> PropertyKind {
+        self.kind
+    }
+
+    #[inline(always)]
+    pub const fn cardinality(&self) -> usize {
+        self.kind.cardinality()
+    }
+}
+
+/// A block state property whose values are of type `V`.
+#[derive(Debug)]
+pub struct Property<V> {
+    key: PropertyKey,
+    _marker: PhantomData<fn() -> V>,
+}
+
+impl<V> Clone for Property<V> {
+    #[inline(always)]
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<V> Copy for Property<V> {}
+
+impl<V> From<Property<V>> for PropertyKey {
+    #[inline(always)]
+    fn from(property: Property<V>) -> Self {
+        property.key
+    }
+}
+
+impl<V> Property<V> {
+    #[inline(always)]
+    pub const fn key(&self) -> PropertyKey {
+        self.key
+    }
+
+    #[inline(always)]
+    pub const fn name(&self) -> &'static str {
+        self.key.name
+    }
+}
+
+impl Property<bool> {
+    #[inline]
+    pub const fn boolean(name: &'static str) -> Self {
+        Self {
+            key: PropertyKey {
+                name,
+                kind: PropertyKind::Bool,
+            },
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl Property<u8> {
+    #[inline]
+    pub const fn int(name: &'static str, min: u8, max: u8) -> Self {
+        assert!(min <= max);
+
+        Self {
+            key: PropertyKey {
+                name,
+                kind: PropertyKind::Int { min, max },
+            },
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<E: StateEnum> Property<E> {
+    #[inline]
+    pub const fn enumeration(name: &'static str) -> Self {
+        Self {
+            key: PropertyKey {
+                name,
+                kind: PropertyKind::Enum(E::NAMES),
+            },
+            _marker: PhantomData,
+        }
+    }
+}
+
+/// Value types that can be stored in a [`Property`].
+pub trait PropertyValue: Copy + 'static {
+    fn to_index(self, kind: &PropertyKind) -> Option<usize>;
+
+    fn from_index(index: usize, kind: &PropertyKind) -> Option<Self>;
+}
+
+/// Enums usable as [`Property`] values, with `NAMES` listed in declaration order.
+pub trait StateEnum: Copy + 'static {
+    const NAMES: &'static [&'static str];
+
+    fn index(self) -> usize;
+
+    fn from_index(index: usize) -> Option<Self>;
+}
+
+impl PropertyValue for bool {
+    #[inline(always)]
+    fn to_index(self, _: &PropertyKind) -> Option<usize> {
+        Some(self as usize)
+    }
+
+    #[inline(always)]
+    fn from_index(index: usize, _: &PropertyKind) -> Option<Self> {
+        match index {
+            0 => Some(false),
+            1 => Some(true),
+            _ => None,
+        }
+    }
+}
+
+impl PropertyValue for u8 {
+    #[inline]
+    fn to_index(self, kind: &PropertyKind) -> Option<usize> {
+        match kind {
+            PropertyKind::Int { min, max } if (*min..=*max).contains(&self) => {
+                Some((self - *min) as usize)
+            }
+            _ => None,
+        }
+    }
+
+    #[inline]
+    fn from_index(index: usize, kind: &PropertyKind) -> Option<Self> {
+        match kind {
+            PropertyKind::Int { min, .. } if index < kind.cardinality() => {
+                Some(*min + index as u8)
+            }
+            _ => None,
+        }
+    }
+}
+
+impl<E: StateEnum> PropertyValue for E {
+    #[inline(always)]
+    fn to_index(self, _: &PropertyKind) -> Option<usize> {
+        Some(self.index())
+    }
+
+    #[inline(always)]
+    fn from_index(index: usize, _: &PropertyKind) -> Option<Self> {
+        E::from_index(index)
+    }
+}
+
+/// The set of properties declared by a [`Block`].
+///
+/// States are laid out so that the last property varies fastest, and the default state of a
+/// block is the one where every property sits at its first value.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct StateDefinition {
+    properties: Box<[PropertyKey]>,
+    strides: Box<[usize]>,
+    count: usize,
+}
+
+impl<const N: usize> From<[PropertyKey; N]> for StateDefinition {
+    #[inline]
+    fn from(properties: [PropertyKey; N]) -> Self {
+        Self::new(Box::new(properties))
+    }
+}
+
+impl From<Box<[PropertyKey]>> for StateDefinition {
+    #[inline]
+    fn from(properties: Box<[PropertyKey]>) -> Self {
+        Self::new(properties)
+    }
+}
+
+impl StateDefinition {
+    #[inline]
+    pub fn new(properties: Box<[PropertyKey]>) -> Self {
+        let mut strides = vec![0usize; properties.len()].into_boxed_slice();
+        let mut count = 1usize;
+
+        for (i, property) in properties.iter().enumerate().rev() {
+            strides[i] = count;
+            count *= property.cardinality();
+        }
+
+        Self {
+            properties,
+            strides,
+            count,
+        }
+    }
+
+    #[inline(always)]
+    pub fn properties(&self) -> &[PropertyKey] {
+        &self.properties
+    }
+
+    #[inline(always)]
+    pub const fn state_count(&self) -> usize {
+        self.count
+    }
+
+    #[inline]
+    pub fn position_of(&self, name: &str) -> Option<usize> {
+        self.properties.iter().position(|p| p.name == name)
+    }
+
+    /// Position of `key`, or `None` if it is missing or its name is declared with another kind.
+    #[inline]
+    pub fn position_of_key(&self, key: PropertyKey) -> Option<usize> {
+        self.properties.iter().position(|p| *p == key)
+    }
+
+    #[inline]
+    pub fn value_index(&self, offset: usize, property: usize) -> usize {
+        (offset / self.strides[property]) % self.properties[property].cardinality()
+    }
+
+    #[inline]
+    pub fn with_value_index(&self, offset: usize, property: usize, index: usize) -> usize {
+        let current = self.value_index(offset, property);
+        let stride = self.strides[property];
+
+        offset - (current * stride) + (index * stride)
+    }
+}
+
+#[derive(Debug, Clone)]
+struct StateRange {
+    base: usize,
+    definition: StateDefinition,
+    flags: BlockFlags,
+    block_entity: Option<BlockEntityType>,
+    ticker: Option<&'static dyn BlockTicker>,
+    callbacks: Option<&'static dyn BlockCallbacks>,
+    light_emission: u8,
+    light_opacity: u8,
+    signal_emission: u8,
+    toughness: f32,
+}
+
+/// Maps every block state id to its owning block.
+///
+/// Each block is assigned a contiguous range of state ids in registry index order, so the
+/// default `air` block always owns state `0`. Once built, the table is immutable and can be
+/// queried without touching the block registry lock.
+#[derive(Debug, Clone)]
+pub struct BlockStates {
+    ranges: Box<[StateRange]>,
+    owners: Box<[usize]>,
+    shapes: Box<[BlockShape]>,
+    full_faces: Box<[u8]>,
+}
+
+impl BlockStates {
+    pub fn build(registry: &impl Registry<Block>) -> Self {
+        let mut ranges = Vec::with_capacity(registry.len());
+        let mut owners = Vec::with_capacity(registry.len());
+        let mut shapes = Vec::with_capacity(registry.len());
+        let mut full_faces = Vec::with_capacity(registry.len());
+
+        for (idx, _, block) in registry.iter_indexed() {
+            let definition = block.states().clone();
+
+            for offset in 0..definition.state_count() {
+                let shape = oriented_shape(block.shapes(), &definition, offset);
+
+                full_faces.push(Direction::ALL.iter().fold(0, |mask, &direction| {
+                    mask | ((shape.covers_face(direction) as u8) << direction as u8)
+                }));
+
+                shapes.push(shape);
+            }
+
+            ranges.push(StateRange {
+                base: owners.len(),
+                definition,
+                flags: block.flags(),
+                block_entity: block.block_entity().copied(),
+                ticker: block.ticker(),
+                callbacks: block.callbacks(),
+                light_emission: block.light_emission(),
+                light_opacity: block.light_opacity(),
+                signal_emission: block.signal_emission(),
+                toughness: block.toughness(),
+            });
+
+            owners.extend(std::iter::repeat_n(idx, ranges[idx].definition.state_count()));
+        }
+
+        Self {
+            ranges: ranges.into_boxed_slice(),
+            owners: owners.into_boxed_slice(),
+            shapes: shapes.into_boxed_slice(),
+            full_faces: full_faces.into_boxed_slice(),
+        }
+    }
+
+    /// Builds the global state table from the block registry, once every block is registered.
+    ///
+    /// Runs in [`RegistrySet::Freeze`]; later calls return the table built first.
+    #[inline]
+    pub fn build_global() -> &'static Self {
+        BLOCK_STATES.get_or_init(|| Self::build(&*Registrar::<Block>::read_from_registry()))
+    }
+
+    /// Builds the global state table from `registry` instead of the global block registry, for
+    /// benches and tests running world logic without the registry plugin. The blocks must be
+    /// registered in the same order as their holders were bound.
+    ///
+    /// Like [`Self::build_global`], later calls return the table installed first.
+    #[inline]
+    pub fn install(registry: &impl Registry<Block>) -> &'static Self {
+        BLOCK_STATES.get_or_init(|| Self::build(registry))
+    }
+
+    /// Returns the global state table.
+    ///
+    /// Panics if it was not built yet by [`Self::build_global`] or [`Self::install`], so
+    /// nothing can see a table missing the blocks loaded from data files.
+    #[inline]
+    pub fn global() -> &'static Self {
+        BLOCK_STATES
+            .get()
+            .expect("block states are only available once the registries are frozen")
+    }
+
+    #[inline(always)]
+    pub fn len(&self) -> usize {
+        self.owners.len()
+    }
+
+    #[inline(always)]
+    pub fn is_empty(&self) -> bool {
+        self.owners.is_empty()
+    }
+
+    #[inline(always)]
+    pub fn block_of(&self, state: usize) -> usize {
+        self.owners.get(state).copied().unwrap_or(0)
+    }
+
+    #[inline]
+    pub fn default_state(&self, block: usize) -> usize {
+        self.ranges.get(block).map(|r| r.base).unwrap_or(0)
+    }
+
+    #[inline]
+    pub fn states_of(&self, block: usize) -> Range<usize> {
+        self.ranges
+            .get(block)
+            .map(|r| r.base..r.base + r.definition.state_count())
+            .unwrap_or(0..0)
+    }
+
+    /// Shape of `state`, turned according to its `axis`, `half` and `facing` properties.
+    #[inline]
+    pub fn shape_of(&self, state: usize) -> &BlockShape {
+        static EMPTY: BlockShape = BlockShape::None;
+
+        self.shapes.get(state).unwrap_or(&EMPTY)
+    }
+
+    /// Whether the shape of `state` fully covers its face towards `direction`, e.g. to tell
+    /// if it can support a block placed against that face.
+    #[inline]
+    pub fn covers_face(&self, state: usize, direction: Direction) -> bool {
+        self.full_faces
+            .get(state)
+            .is_some_and(|mask| mask & (1 << direction as u8) != 0)
+    }
+
+    /// Whether `state` can support a block resting on top of it, i.e. covers its upper face.
+    #[inline]
+    pub fn can_support(&self, state: usize) -> bool {
+        self.covers_face(state, Direction::PosY)
+    }
+
+    #[inline]
+    pub fn flags_of(&self, block: usize) -> BlockFlags {
+        self.ranges
+            .get(block)
+            .map(|r| r.flags)
+            .unwrap_or(BlockFlags::AIR)
+    }
+
+    #[inline]
+    pub fn light_emission(&self, state: usize) -> u8 {
+        self.ranges[self.block_of(state)].light_emission
+    }
+
+    #[inline]
+    pub fn light_opacity(&self, state: usize) -> u8 {
+        self.ranges[self.block_of(state)].light_opacity
+    }
+
+    #[inline]
+    pub fn signal_emission(&self, state: usize) -> u8 {
+        self.ranges[self.block_of(state)].signal_emission
+    }
+
+    #[inline]
+    pub fn toughness(&self, state: usize) -> f32 {
+        self.ranges[self.block_of(state)].toughness
+    }
+
+    #[inline]
+    pub fn block_entity_of(&self, block: usize) -> Option<&BlockEntityType> {
+        self.ranges.get(block)?.block_entity.as_ref()
+    }
+
+    #[inline]
+    pub fn ticker_of(&self, block: usize) -> Option<&'static dyn BlockTicker> {
+        self.ranges.get(block)?.ticker
+    }
+
+    #[inline]
+    pub fn callbacks_of(&self, block: usize) -> Option<&'static dyn BlockCallbacks> {
+        self.ranges.get(block)?.callbacks
+    }
+
+    #[inline]
+    pub fn definition_of(&self, block: usize) -> Option<&StateDefinition> {
+        self.ranges.get(block).map(|r| &r.definition)
+    }
+
+    /// Value of `property` in `state`, or `None` if the block of `state` does not declare
+    /// `property` with the same kind.
+    #[inline]
+    pub fn value<V: PropertyValue>(&self, state: usize, property: Property<V>) -> Option<V> {
+        let range = self.ranges.get(self.owners.get(state).copied()?)?;
+        let position = range.definition.position_of_key(property.key())?;
+
+        let index = range.definition.value_index(state - range.base, position);
+
+        V::from_index(index, &property.key.kind)
+    }
+
+    /// `state` with `property` set to `value`, or `None` if the block of `state` does not declare
+    /// `property` with the same kind, or `value` is out of its range.
+    #[inline]
+    pub fn with<V: PropertyValue>(
+        &self,
+        state: usize,
+        property: Property<V>,
+        value: V,
+    ) -> Option<usize> {
+        let range = self.ranges.get(self.owners.get(state).copied()?)?;
+        let position = range.definition.position_of_key(property.key())?;
+
+        let index = value.to_index(&property.key.kind)?;
+
+        if index >= range.definition.properties[position].cardinality() {
+            return None;
+        }
+
+        let offset = range
+            .definition
+            .with_value_index(state - range.base, position, index);
+
+        Some(range.base + offset)
+    }
+}
+
+/// Orients the block's shape for the state at `offset`. Shapes are authored along `Y`, as the
+/// bottom half and facing north. Open blocks stand the shape up against their facing side.
+fn oriented_shape(shape: &BlockShape, definition: &StateDefinition, offset: usize) -> BlockShape {
+    let value = |property: PropertyKey| {
+        definition
+            .position_of_key(property)
+            .map(|position| definition.value_index(offset, position))
+    };
+
+    let mut shape = match value(AXIS.key()).and_then(<Axis as StateEnum>::from_index) {
+        Some(axis) => shape.aligned_to(axis),
+        None => shape.clone(),
+    };
+
+    let open = value(OPEN.key()) == Some(true as usize);
+
+    if open {
+        shape = shape.aligned_to(Axis::Z);
+    } else if let Some(Half::Top) = value(HALF.key()).and_then(<Half as StateEnum>::from_index) {
+        shape = shape.flipped_vertically();
+    }
+
+    if let Some(facing) = value(FACING.key()).and_then(<Facing as StateEnum>::from_index) {
+        shape = shape.rotated(facing.into());
+    }
+
+    shape
+}
+
+impl BlockStates {
+    /// Captures the state layout of every block so stored state ids can be remapped later,
+    /// even if blocks are reordered or their properties change.
+    pub fn snapshot(&self, registry: &impl Registry<Block>) -> StateSnapshot {
+        let blocks = self
+            .ranges
+            .iter()
+            .enumerate()
+            .map(|(idx, range)| BlockStateLayout {
+                key: registry.idx_to_key(idx).unwrap().clone(),
+                properties: range
+                    .definition
+                    .properties()
+                    .iter()
+                    .map(|p| PropertyLayout {
+                        name: Box::from(p.name()),
+                        values: (0..p.cardinality())
+                            .map(|i| p.kind().value_name(i).unwrap())
+                            .collect(),
+                    })
+                    .collect(),
+            })
+            .collect();
+
+        StateSnapshot { blocks }
+    }
+}
+
+/// Persistable description of a [`BlockStates`] table.
+///
+/// Remapping matches blocks by key and properties by name; values that no longer exist fall
+/// back to the property's first value.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, Eq, PartialEq)]
+pub struct StateSnapshot {
+    blocks: Vec<BlockStateLayout>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
+struct BlockStateLayout {
+    key: AssetLocation,
+    properties: Vec<PropertyLayout>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
+struct PropertyLayout {
+    name: Box<str>,
+    values: Vec<Box<str>>,
+}
+
+impl StateSnapshot {
+    pub fn remap(
+        &self,
+        registry: &impl Registry<Block>,
+        states: &BlockStates,
+        policy: MissingKeyPolicy,
+    ) -> Result<IdRemap, RemapError> {
+        let mut table = Vec::new();
+
+        for layout in &self.blocks {
+            let count = layout
+                .properties
+                .iter()
+                .map(|p| p.values.len())
+                .product::<usize>();
+
+            let Some(block) = registry.key_to_idx(&layout.key) else {
+                let fallback = policy.resolve(registry, &layout.key)?;
+
+                table.extend(std::iter::repeat_n(states.default_state(fallback), count));
+
+                continue;
+            };
+
+            let base = states.default_state(block);
+            let definition = states.definition_of(block).unwrap();
+
+            for offset in 0..count {
+                let mut remaining = offset;
+                let mut new_offset = 0usize;
+
+                for property in layout.properties.iter().rev() {
+                    let value = &property.values[remaining % property.values.len()];
+                    remaining /= property.values.len();
+
+                    let Some(position) = definition.position_of(&property.name) else {
+                        continue;
+                    };
+
+                    let kind = definition.properties()[position].kind();
+
+                    if let Some(index) = kind.index_of(value) {
+                        new_offset = definition.with_value_index(new_offset, position, index);
+                    }
+                }
+
+                table.push(base + new_offset);
+            }
+        }
+
+        Ok(IdRemap::new(table))
+    }
+}
+
+impl Holder<'_, Block> {
+    /// Default state of this block in the global [`BlockStates`], which must be built or
+    /// installed already.
+    #[inline]
+    pub fn default_state(&self) -> usize {
+        BlockStates::global().default_state(**self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::{
+            consts::{FULL_SHAPE, SLAB_SHAPE},
+            properties::WATERLOGGED,
+        },
+    };
+
+    fn stair() -> Block {
+        Block::new()
+            .states([FACING.key(), HALF.key(), WATERLOGGED.key()])
+            .build()
+    }
+
+    fn registry() -> DefaultedRegistry<Block> {
+        let mut registry = DefaultedRegistry::new(AssetLocation::parse("air"), Block::default());
+
+        registry
+            .register(AssetLocation::parse("stone"), Block::default())
+            .unwrap();
+        registry
+            .register(AssetLocation::parse("stair"), stair())
+            .unwrap();
+
+        registry
+    }
+
+    #[test]
+    fn last_property_varies_fastest() {
+        let definition = StateDefinition::from([FACING.key(), HALF.key(), WATERLOGGED.key()]);
+
+        assert_eq!(definition.state_count(), 16);
+
+        // facing = south, half = top, waterlogged = true
+        let offset = 4 + 2 + 1;
+
+        assert_eq!(definition.value_index(offset, 0), 1);
+        assert_eq!(definition.value_index(offset, 1), 1);
+        assert_eq!(definition.value_index(offset, 2), 1);
+        assert_eq!(definition.with_value_index(offset, 0, 3), 15);
+        assert_eq!(definition.with_value_index(offset, 2, 0), 6);
+    }
+
+    #[test]
+    fn blocks_own_contiguous_state_ranges() {
+        let states = BlockStates::build(&registry());
+
+        assert_eq!(states.len(), 18);
+        assert_eq!(states.default_state(0), 0);
+        assert_eq!(states.default_state(1), 1);
+        assert_eq!(states.states_of(2), 2..18);
+        assert_eq!(states.block_of(17), 2);
+        assert_eq!(states.block_of(18), 0);
+    }
+
+    #[test]
+    fn reads_and_writes_property_values() {
+        let states = BlockStates::build(&registry());
+        let base = states.default_state(2);
+
+        assert_eq!(states.value(base, FACING), Some(Facing::North));
+        assert_eq!(states.value(base, HALF), Some(Half::Bottom));
+
+        let top = states.with(base, HALF, Half::Top).unwrap();
+        let east = states.with(top, FACING, Facing::East).unwrap();
+
+        assert_eq!(top, base + 2);
+        assert_eq!(states.value(east, HALF), Some(Half::Top));
+        assert_eq!(states.value(east, FACING), Some(Facing::East));
+        assert_eq!(states.with(states.default_state(1), HALF, Half::Top), None);
+        assert_eq!(states.value(states.default_state(1), FACING), None);
+    }
+
+    #[test]
+    fn ignores_properties_of_another_kind() {
+        const BOOL_FACING: Property<bool> = Property::boolean("facing");
+
+        let mut registry = DefaultedRegistry::new(AssetLocation::parse("air"), Block::default());
+
+        registry
+            .register(
+                AssetLocation::parse("lever"),
+                Block::new().states([BOOL_FACING.key()]).build(),
+            )
+            .unwrap();
+
+        let states = BlockStates::build(&registry);
+        let lever = states.default_state(1);
+
+        assert_eq!(states.value(lever, FACING), None);
+        assert_eq!(states.with(lever, FACING, Facing::East), None);
+        assert_eq!(states.value(lever, BOOL_FACING), Some(false));
+        assert_eq!(states.with(lever, BOOL_FACING, true), Some(lever + 1));
+    }
+
+    #[test]
+    fn support_follows_the_shape_of_each_state() {
+        let mut registry = DefaultedRegistry::new(AssetLocation::parse("air"), Block::default());
+
+        registry
+            .register(
+                AssetLocation::parse("stone"),
+                Block::new().shape(FULL_SHAPE).build(),
+            )
+            .unwrap();
+        registry
+            .register(
+                AssetLocation::parse("slab"),
+                Block::new().shape(SLAB_SHAPE).states([HALF.key()]).build(),
+            )
+            .unwrap();
+
+        let states = BlockStates::build(&registry);
+        let bottom = states.default_state(2);
+        let top = states.with(bottom, HALF, Half::Top).unwrap();
+
+        assert!(!states.can_support(states.default_state(0)));
+        assert!(states.can_support(states.default_state(1)));
+        assert!(!states.can_support(bottom));
+        assert!(states.can_support(top));
+    }
+
+    #[test]
+    fn remaps_states_across_layouts() {
+        let old = registry();
+        let snapshot = BlockStates::build(&old).snapshot(&old);
+
+        let mut new = DefaultedRegistry::new(AssetLocation::parse("air"), Block::default());
+
+        new.register(
+            AssetLocation::parse("stair"),
+            Block::new()
+                .states([WATERLOGGED.key(), FACING.key()])
+                .build(),
+        )
+        .unwrap();
+
+        let states = BlockStates::build(&new);
+        let remap = snapshot
+            .remap(&new, &states, MissingKeyPolicy::ReplaceWithDefault)
+            .unwrap();
+
+        // facing = east, half = top, waterlogged = true
+        let stored = 2 + 2 * 4 + 2 + 1;
+        let current = states
+            .with(states.default_state(1), FACING, Facing::East)
+            .and_then(|state| states.with(state, WATERLOGGED, true))
+            .unwrap();
+
+        assert_eq!(remap.len(), 18);
+        assert_eq!(remap.get(0), Some(0));
+        assert_eq!(remap.get(1), Some(0));
+        assert_eq!(remap.get(stored), Some(current));
+
+        assert_eq!(
+            snapshot.remap(&new, &states, MissingKeyPolicy::Fail),
+            Err(RemapError::MissingKey(AssetLocation::parse("stone")))
+        );
+    }
+}
@@ -0,0 +1,26 @@
+use {
+    crate::prelude::{Block, TagKey},
+    std::sync::LazyLock,
+};
+
+macro_rules! block_tags {
+    ($($name:ident = $location:literal;)*) => {
+        $(
+            pub static $name: LazyLock<TagKey<Block>> =
+                LazyLock::new(|| TagKey::parse($location));
+        )*
+    };
+}
+
+block_tags! {
+    LOGS = "bevycraft:logs";
+    LEAVES = "bevycraft:leaves";
+    PLANKS = "bevycraft:planks";
+    DIRT = "bevycraft:dirt";
+    SAND = "bevycraft:sand";
+    FLOWERS = "bevycraft:flowers";
+    REPLACEABLE = "bevycraft:replaceable";
+    MINEABLE_AXE = "bevycraft:mineable/axe";
+    MINEABLE_PICKAXE = "bevycraft:mineable/pickaxe";
+    MINEABLE_SHOVEL = "bevycraft:mineable/shovel";
+}
@@ -0,0 +1,60 @@
+use {
+    crate::prelude::{BlockLevel, BlockStates, Direction},
+    bevy::math::IVec3,
+    std::fmt::Debug,
+};
+
+/// Lets a block evolve over time.
+///
+/// Random ticks hit random positions of every loaded chunk, scheduled ticks run once the delay
+/// given to [`BlockLevel::schedule_tick`] has passed.
+pub trait BlockTicker: Debug + Send + Sync {
+    #[allow(unused_variables)]
+    fn random_tick(&self, level: &mut dyn BlockLevel, position: IVec3, state: usize) {}
+
+    #[allow(unused_variables)]
+    fn scheduled_tick(&self, level: &mut dyn BlockLevel, position: IVec3, state: usize) {}
+}
+
+impl PartialEq for dyn BlockTicker {
+    #[inline(always)]
+    fn eq(&self, other: &Self) -> bool {
+        std::ptr::addr_eq(self, other)
+    }
+}
+
+/// Spreads a block onto nearby `target` blocks that are not covered, and turns it back into
+/// `decay` once covered itself, like grass on dirt.
+#[derive(Debug)]
+pub struct SpreadingTicker {
+    pub target: fn() -> usize,
+    pub decay: fn() -> usize,
+}
+
+impl BlockTicker for SpreadingTicker {
+    fn random_tick(&self, level: &mut dyn BlockLevel, position: IVec3, state: usize) {
+        if is_covered(level, position) {
+            level.set_state(position, (self.decay)());
+
+            return;
+        }
+
+        let offset = IVec3::new(
+            level.random(3) as i32 - 1,
+            level.random(5) as i32 - 3,
+            level.random(3) as i32 - 1,
+        );
+        let candidate = position + offset;
+
+        if level.get_state(candidate) == Some((self.target)()) && !is_covered(level, candidate) {
+            level.set_state(candidate, state);
+        }
+    }
+}
+
+/// Whether the block above `position` covers its top face.
+fn is_covered(level: &dyn BlockLevel, position: IVec3) -> bool {
+    level
+        .get_state(position + Direction::PosY.offset())
+        .is_some_and(|above| BlockStates::global().covers_face(above, Direction::NegY))
+}
@@ -31,8 +31,5 @@ pub const TRAPDOOR_SHAPE: [Aabb3d; 1] = [Aabb3d {
 }];
 
 pub const FULL_BLOCK: LazyLock<BlockFlags> = LazyLock::new(|| {
-    BlockFlags::OCCLUDABLE
-        | BlockFlags::COLLIDABLE
-        | BlockFlags::DOES_SPAWN
-        | BlockFlags::CAN_SUPPORT
+    BlockFlags::OCCLUDABLE | BlockFlags::COLLIDABLE | BlockFlags::DOES_SPAWN
 });
@@ -0,0 +1,44 @@
+use crate::prelude::*;
+
+/// Registers an item for every non-air block in `blocks` under the block's key, placing that
+/// block and named `block.<namespace>.<path>`.
+///
+/// Keys that already have an item are left untouched, so hand-written items take precedence.
+/// Returns the number of items generated.
+pub fn register_block_items(
+    blocks: &impl Registry<Block>,
+    items: &mut impl Registry<Item>,
+) -> Result<usize, RegistrationError> {
+    let mut count = 0;
+
+    for (idx, location, block) in blocks.iter_indexed() {
+        if block.air() {
+            continue;
+        }
+
+        if items.contains_key(location) {
+            continue;
+        }
+
+        let item = Item::new()
+            .name_key(name_key("block", location))
+            .block(Some(idx))
+            .build();
+
+        items.register(location.clone(), item)?;
+        count += 1;
+    }
+
+    Ok(count)
+}
+
+/// Builds a translation key such as `block.bevycraft.oak_log` from `location`.
+#[inline]
+fn name_key(kind: &str, location: &AssetLocation) -> String {
+    format!(
+        "{}.{}.{}",
+        kind,
+        location.namespace(),
+        location.path().replace('/', ".")
+    )
+}
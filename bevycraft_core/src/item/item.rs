@@ -0,0 +1,57 @@
+use {bevycraft_macros::Registrar, builder_pattern::Builder};
+
+pub const DEFAULT_MAX_STACK_SIZE: u8 = 64;
+
+#[derive(Registrar, Builder, Debug, PartialEq)]
+#[registrar(default = "air")]
+pub struct Item {
+    #[into]
+    #[public]
+    #[default(DEFAULT_MAX_STACK_SIZE)]
+    max_stack_size: u8,
+
+    /// Translation key of the display name, e.g. `item.bevycraft.stick`.
+    #[into]
+    #[public]
+    #[default(Box::from(""))]
+    name_key: Box<str>,
+
+    /// Index into `Registrar::<Block>` of the block placed by this item.
+    #[into]
+    #[public]
+    #[default(None)]
+    block: Option<usize>,
+}
+
+impl Default for Item {
+    #[inline(always)]
+    fn default() -> Self {
+        Self {
+            max_stack_size: 0,
+            name_key: Box::from("item.bevycraft.air"),
+            block: None,
+        }
+    }
+}
+
+impl Item {
+    #[inline(always)]
+    pub const fn max_stack_size(&self) -> u8 {
+        self.max_stack_size
+    }
+
+    #[inline(always)]
+    pub const fn stackable(&self) -> bool {
+        self.max_stack_size > 1
+    }
+
+    #[inline(always)]
+    pub fn name_key(&self) -> &str {
+        &self.name_key
+    }
+
+    #[inline(always)]
+    pub const fn block(&self) -> Option<usize> {
+        self.block
+    }
+}
@@ -0,0 +1,3 @@
+pub mod block_items;
+pub mod item;
+pub mod stack;
@@ -0,0 +1,53 @@
+use crate::prelude::{Item, Registry};
+
+/// A count of a single item, e.g. a slot of an inventory.
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct ItemStack {
+    /// Index into `Registrar::<Item>`.
+    pub item: usize,
+    pub count: u8,
+}
+
+impl ItemStack {
+    pub const EMPTY: Self = Self { item: 0, count: 0 };
+
+    #[inline(always)]
+    pub const fn new(item: usize, count: u8) -> Self {
+        Self { item, count }
+    }
+
+    /// Whether the stack holds nothing. Stacks of the default item are always empty.
+    #[inline(always)]
+    pub const fn is_empty(&self) -> bool {
+        self.item == 0 || self.count == 0
+    }
+
+    /// Adds as much of `other` as fits under the max stack size of the item, returning what is
+    /// left of `other`.
+    pub fn merge(&mut self, other: Self, items: &impl Registry<Item>) -> Self {
+        if other.is_empty() {
+            return Self::EMPTY;
+        }
+
+        if self.is_empty() {
+            *self = Self::new(other.item, 0);
+        }
+
+        if self.item != other.item {
+            return other;
+        }
+
+        let max = items
+            .get_by_idx(self.item)
+            .map(|item| item.max_stack_size())
+            .unwrap_or(0);
+
+        let moved = other.count.min(max.saturating_sub(self.count));
+        self.count += moved;
+
+        match other.count - moved {
+            0 => Self::EMPTY,
+            left => Self::new(other.item, left),
+        }
+    }
+}
@@ -2,17 +2,38 @@ extern crate core;
 
 mod block;
 pub mod consts;
+mod direction;
+mod item;
+mod loot;
 mod memory;
+mod recipe;
 mod registries;
 mod util;
 
 pub mod prelude {
     pub use crate::{
-        block::{behaviour::*, block::*, flags::*, shape::BlockShape},
-        memory::pattern_container::{PatternContainer, PatternIter},
+        block::{
+            behaviour::*,
+            block::*,
+            callbacks::*,
+            definition::*,
+            entity::*,
+            flags::*,
+            fluid::*,
+            level::*,
+            shape::{BlockMirror, BlockRotation, BlockShape, ShapeHit},
+            signal::*,
+            state::*,
+            ticker::*,
+        },
+        direction::Direction,
+        item::{block_items::*, item::*, stack::*},
+        loot::{loader::*, table::*},
+        memory::pattern_container::{PackingLayout, PatternContainer, PatternIter},
+        recipe::{loader::*, matcher::*, recipe::*},
         registries::{
             asset_location::*, defaulted_registry::*, holder::Holder, ordered_registry::*,
-            registrar::*, registry::*,
+            plugin::*, registrar::*, registry::*, snapshot::*, tag::*,
         },
     };
 }
@@ -20,3 +41,11 @@ pub mod prelude {
 pub mod blocks {
     pub use crate::block::blocks::*;
 }
+
+pub mod properties {
+    pub use crate::block::properties::*;
+}
+
+pub mod tags {
+    pub use crate::block::tags::*;
+}
@@ -0,0 +1,359 @@
+use {
+    crate::{
+        prelude::*,
+        util::files::{collect_files, read_dir_sorted, relative_asset_path},
+    },
+    serde::Deserialize,
+    std::{
+        fs,
+        path::{Path, PathBuf},
+    },
+    thiserror::Error,
+};
+
+const LOOT_TABLES_DIRECTORY: &str = "loot_tables";
+
+const BLOCKS_DIRECTORY: &str = "blocks";
+
+const LOOT_TABLE_EXTENSION: &str = "ron";
+
+/// Contents of a `<namespace>/loot_tables/blocks/*.ron` file, named after its block.
+#[derive(Deserialize, Debug, Clone)]
+#[serde(deny_unknown_fields)]
+struct LootTableFile {
+    pools: Vec<LootPoolFile>,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+#[serde(deny_unknown_fields)]
+struct LootPoolFile {
+    #[serde(default = "default_count")]
+    rolls: CountRangeFile,
+
+    #[serde(default)]
+    conditions: Vec<LootConditionFile>,
+
+    entries: Vec<LootEntryFile>,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+enum LootEntryFile {
+    Item {
+        name: Box<str>,
+
+        #[serde(default = "default_weight")]
+        weight: u32,
+
+        #[serde(default = "default_count")]
+        count: CountRangeFile,
+    },
+    Empty {
+        #[serde(default = "default_weight")]
+        weight: u32,
+    },
+}
+
+#[derive(Deserialize, Debug, Clone)]
+enum LootConditionFile {
+    MatchTool(Box<str>),
+    NotTool(Box<str>),
+    RandomChance(f32),
+}
+
+/// Either an exact count or `(min: _, max: _)`.
+#[derive(Deserialize, Debug, Copy, Clone)]
+#[serde(untagged)]
+enum CountRangeFile {
+    Exactly(u8),
+    Range { min: u8, max: u8 },
+}
+
+impl CountRangeFile {
+    fn resolve(self, location: &AssetLocation) -> Result<CountRange, LootTableError> {
+        match self {
+            Self::Exactly(count) => Ok(CountRange::exactly(count)),
+            Self::Range { min, max } if min <= max => Ok(CountRange { min, max }),
+            Self::Range { min, max } => Err(LootTableError::InvalidRange {
+                location: location.clone(),
+                min,
+                max,
+            }),
+        }
+    }
+}
+
+/// Loads every `<namespace>/loot_tables/blocks/**/*.ron` file under `assets`.
+///
+/// Non-air blocks without a table drop the item registered under their own key, if there is
+/// one. Later namespaces replace the tables of earlier ones.
+pub fn load_block_loot_tables(
+    blocks: &impl Registry<Block>,
+    items: &impl Registry<Item>,
+    assets: &Path,
+) -> Result<LootTables, LootTableError> {
+    let mut tables: Vec<Option<LootTable>> = vec![None; blocks.len()];
+
+    for namespace in read_dir_sorted(assets)? {
+        let root = namespace
+            .join(LOOT_TABLES_DIRECTORY)
+            .join(BLOCKS_DIRECTORY);
+
+        let Some(namespace) = namespace.file_name().and_then(|n| n.to_str()) else {
+            continue;
+        };
+
+        let mut files = Vec::new();
+        collect_files(&root, LOOT_TABLE_EXTENSION, &mut files)?;
+
+        for file in files {
+            let relative = relative_asset_path(&root, &file).unwrap();
+            let location = AssetLocation::try_with_custom_namespace(namespace, &relative)?;
+
+            let Some(block) = blocks.key_to_idx(&location) else {
+                return Err(LootTableError::UnknownBlock(location));
+            };
+
+            let bytes = fs::read(&file)?;
+            let parsed = ron::de::from_bytes::<LootTableFile>(&bytes)
+                .map_err(|e| LootTableError::Deserialize(file.clone(), Box::new(e)))?;
+
+            tables[block] = Some(resolve_table(parsed, &location, items)?);
+        }
+    }
+
+    for (idx, location, block) in blocks.iter_indexed() {
+        if block.air() || tables[idx].is_some() {
+            continue;
+        }
+
+        tables[idx] = items.key_to_idx(location).map(LootTable::single);
+    }
+
+    Ok(LootTables {
+        blocks: tables.into_boxed_slice(),
+    })
+}
+
+fn resolve_table(
+    file: LootTableFile,
+    location: &AssetLocation,
+    items: &impl Registry<Item>,
+) -> Result<LootTable, LootTableError> {
+    let mut pools = Vec::with_capacity(file.pools.len());
+
+    for pool in file.pools {
+        let mut entries = Vec::with_capacity(pool.entries.len());
+
+        for entry in pool.entries {
+            entries.push(match entry {
+                LootEntryFile::Item {
+                    name,
+                    weight,
+                    count,
+                } => {
+                    let item = AssetLocation::try_parsing(&name)?;
+
+                    let Some(idx) = items.key_to_idx(&item) else {
+                        return Err(LootTableError::UnknownItem {
+                            location: location.clone(),
+                            item,
+                        });
+                    };
+
+                    LootEntry {
+                        item: Some(idx),
+                        weight,
+                        count: count.resolve(location)?,
+                    }
+                }
+                LootEntryFile::Empty { weight } => LootEntry {
+                    item: None,
+                    weight,
+                    count: CountRange::exactly(0),
+                },
+            });
+        }
+
+        let conditions = pool
+            .conditions
+            .into_iter()
+            .map(|condition| -> Result<LootCondition, LootTableError> {
+                Ok(match condition {
+                    LootConditionFile::MatchTool(tag) => {
+                        LootCondition::MatchTool(TagKey::try_parsing(&tag)?)
+                    }
+                    LootConditionFile::NotTool(tag) => {
+                        LootCondition::NotTool(TagKey::try_parsing(&tag)?)
+                    }
+                    LootConditionFile::RandomChance(chance) => {
+                        LootCondition::RandomChance(chance.clamp(0.0, 1.0))
+                    }
+                })
+            })
+            .collect::<Result<Box<[_]>, LootTableError>>()?;
+
+        pools.push(LootPool {
+            rolls: pool.rolls.resolve(location)?,
+            conditions,
+            entries: entries.into_boxed_slice(),
+        });
+    }
+
+    Ok(LootTable {
+        pools: pools.into_boxed_slice(),
+    })
+}
+
+#[inline(always)]
+const fn default_count() -> CountRangeFile {
+    CountRangeFile::Exactly(1)
+}
+
+#[inline(always)]
+const fn default_weight() -> u32 {
+    1
+}
+
+#[derive(Error, Debug)]
+pub enum LootTableError {
+    #[error("io error: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("failed to deserialize `{path}`: {error}", path = .0.display(), error = .1)]
+    Deserialize(PathBuf, Box<ron::de::SpannedError>),
+
+    #[error("invalid location: {0}")]
+    InvalidLocation(#[from] AssetLocationError),
+
+    #[error("loot table for unknown block `{0}`")]
+    UnknownBlock(AssetLocation),
+
+    #[error("loot table `{location}` drops unknown item `{item}`")]
+    UnknownItem {
+        location: AssetLocation,
+        item: AssetLocation,
+    },
+
+    #[error("loot table `{location}` has an empty range {min}..={max}")]
+    InvalidRange {
+        location: AssetLocation,
+        min: u8,
+        max: u8,
+    },
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, crate::blocks::register_blocks};
+
+    /// Writes each `(block, table)` as a block loot table of the default namespace under a fresh
+    /// assets directory named after `test`.
+    fn write_tables(test: &str, tables: &[(&str, &str)]) -> PathBuf {
+        let assets =
+            std::env::temp_dir().join(format!("bevycraft_loot_{test}_{}", std::process::id()));
+
+        let _ = fs::remove_dir_all(&assets);
+
+        for (block, table) in tables {
+            let file = assets
+                .join(AssetLocation::DEFAULT_NAMESPACE)
+                .join(LOOT_TABLES_DIRECTORY)
+                .join(BLOCKS_DIRECTORY)
+                .join(format!("{block}.{LOOT_TABLE_EXTENSION}"));
+
+            fs::create_dir_all(file.parent().unwrap()).unwrap();
+            fs::write(file, table).unwrap();
+        }
+
+        assets
+    }
+
+    fn blocks() -> DefaultedRegistry<Block> {
+        let mut blocks = Registrar::<Block>::isolated();
+
+        register_blocks(&mut blocks).unwrap();
+
+        blocks
+    }
+
+    fn items() -> DefaultedRegistry<Item> {
+        let mut items = Registrar::<Item>::isolated();
+
+        for key in ["dirt", "stone", "cobblestone"] {
+            items
+                .register(AssetLocation::parse(key), Item::new().build())
+                .unwrap();
+        }
+
+        items
+    }
+
+    fn load(test: &str, tables: &[(&str, &str)]) -> Result<LootTables, LootTableError> {
+        load_block_loot_tables(&blocks(), &items(), &write_tables(test, tables))
+    }
+
+    fn idx<T: Registrable>(registry: &impl Registry<T>, key: &str) -> usize {
+        registry.key_to_idx(&AssetLocation::parse(key)).unwrap()
+    }
+
+    #[test]
+    fn blocks_without_a_table_drop_themselves() {
+        let tables = load(
+            "defaults",
+            &[(
+                "stone",
+                r#"(pools: [(entries: [Item(name: "cobblestone", count: (min: 1, max: 2))])])"#,
+            )],
+        )
+        .unwrap();
+
+        let (blocks, items) = (blocks(), items());
+
+        assert_eq!(
+            tables.block_table(idx(&blocks, "dirt")),
+            Some(&LootTable::single(idx(&items, "dirt")))
+        );
+        assert_eq!(
+            tables.block_table(idx(&blocks, "stone")).unwrap().pools[0].entries[0],
+            LootEntry {
+                item: Some(idx(&items, "cobblestone")),
+                weight: 1,
+                count: CountRange { min: 1, max: 2 },
+            }
+        );
+        assert_eq!(tables.block_table(idx(&blocks, "air")), None);
+        assert_eq!(tables.block_table(idx(&blocks, "gravel")), None);
+    }
+
+    #[test]
+    fn rejects_empty_ranges() {
+        let table = r#"(pools: [(rolls: (min: 3, max: 1), entries: [])])"#;
+
+        assert!(matches!(
+            load("invalid_range", &[("stone", table)]),
+            Err(LootTableError::InvalidRange { min: 3, max: 1, .. })
+        ));
+    }
+
+    #[test]
+    fn rejects_unknown_items() {
+        assert!(matches!(
+            load(
+                "unknown_item",
+                &[("stone", r#"(pools: [(entries: [Item(name: "diamond")])])"#)],
+            ),
+            Err(LootTableError::UnknownItem { item, .. }) if item == AssetLocation::parse("diamond")
+        ));
+    }
+
+    #[test]
+    fn rejects_unknown_blocks() {
+        assert!(matches!(
+            load(
+                "unknown_block",
+                &[("marble", r#"(pools: [(entries: [Item(name: "stone")])])"#)],
+            ),
+            Err(LootTableError::UnknownBlock(block)) if block == AssetLocation::parse("marble")
+        ));
+    }
+}
@@ -0,0 +1,331 @@
+use {
+    crate::prelude::{Item, ItemStack, Registry, TagKey},
+    bevy::ecs::resource::Resource,
+    fastrand::Rng,
+};
+
+/// An inclusive range of counts, e.g. how many items an entry drops.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct CountRange {
+    pub min: u8,
+    pub max: u8,
+}
+
+impl CountRange {
+    pub const ONE: Self = Self::exactly(1);
+
+    #[inline(always)]
+    pub const fn exactly(count: u8) -> Self {
+        Self {
+            min: count,
+            max: count,
+        }
+    }
+
+    #[inline]
+    pub fn roll(&self, rng: &mut Rng) -> u8 {
+        rng.u8(self.min..=self.max.max(self.min))
+    }
+}
+
+/// Decides whether a pool is rolled at all.
+#[derive(Debug, Clone, PartialEq)]
+pub enum LootCondition {
+    /// The tool used is in the given item tag.
+    MatchTool(TagKey<Item>),
+    /// No tool, or a tool outside the given item tag, was used.
+    NotTool(TagKey<Item>),
+    /// Passes with the given probability, from `0.0` to `1.0`.
+    RandomChance(f32),
+}
+
+impl LootCondition {
+    fn test(&self, context: &LootContext, items: &impl Registry<Item>, rng: &mut Rng) -> bool {
+        match self {
+            Self::MatchTool(tag) => context.tool.is_some_and(|tool| items.is_in_tag(tool, tag)),
+            Self::NotTool(tag) => !context.tool.is_some_and(|tool| items.is_in_tag(tool, tag)),
+            Self::RandomChance(chance) => rng.f32() < *chance,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct LootEntry {
+    /// Index into `Registrar::<Item>`, or `None` for an entry dropping nothing.
+    pub item: Option<usize>,
+    pub weight: u32,
+    pub count: CountRange,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct LootPool {
+    pub rolls: CountRange,
+    pub conditions: Box<[LootCondition]>,
+    pub entries: Box<[LootEntry]>,
+}
+
+impl LootPool {
+    fn roll(
+        &self,
+        context: &LootContext,
+        items: &impl Registry<Item>,
+        rng: &mut Rng,
+        drops: &mut Vec<ItemStack>,
+    ) {
+        if !self.conditions.iter().all(|c| c.test(context, items, rng)) {
+            return;
+        }
+
+        // Summed as `u64` so weights read from files cannot overflow.
+        let total: u64 = self.entries.iter().map(|e| e.weight as u64).sum();
+
+        if total == 0 {
+            return;
+        }
+
+        for _ in 0..self.rolls.roll(rng) {
+            let mut pick = rng.u64(0..total);
+
+            let Some(entry) = self.entries.iter().find(|e| {
+                if pick < e.weight as u64 {
+                    return true;
+                }
+
+                pick -= e.weight as u64;
+                false
+            }) else {
+                continue;
+            };
+
+            let Some(item) = entry.item else {
+                continue;
+            };
+
+            let count = entry.count.roll(rng);
+
+            if count > 0 {
+                push_drop(drops, ItemStack::new(item, count));
+            }
+        }
+    }
+}
+
+/// What a block drops when broken, as a list of independently rolled pools.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LootTable {
+    pub pools: Box<[LootPool]>,
+}
+
+impl LootTable {
+    /// A table always dropping a single `item`.
+    #[inline]
+    pub fn single(item: usize) -> Self {
+        Self {
+            pools: Box::new([LootPool {
+                rolls: CountRange::ONE,
+                conditions: Box::new([]),
+                entries: Box::new([LootEntry {
+                    item: Some(item),
+                    weight: 1,
+                    count: CountRange::ONE,
+                }]),
+            }]),
+        }
+    }
+
+    /// Rolls every pool. The same `context` always yields the same drops.
+    pub fn roll(&self, context: &LootContext, items: &impl Registry<Item>) -> Vec<ItemStack> {
+        let mut rng = Rng::with_seed(context.seed);
+        let mut drops = Vec::new();
+
+        for pool in &self.pools {
+            pool.roll(context, items, &mut rng, &mut drops);
+        }
+
+        drops
+    }
+}
+
+/// Inputs of a loot roll.
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq)]
+pub struct LootContext {
+    /// Index into `Registrar::<Item>` of the tool used, if any.
+    pub tool: Option<usize>,
+    pub seed: u64,
+}
+
+/// Loot tables of every block, indexed like `Registrar::<Block>`.
+#[derive(Resource, Debug, Clone, Default)]
+pub struct LootTables {
+    pub(crate) blocks: Box<[Option<LootTable>]>,
+}
+
+impl LootTables {
+    #[inline]
+    pub fn block_table(&self, block: usize) -> Option<&LootTable> {
+        self.blocks.get(block)?.as_ref()
+    }
+
+    /// Drops of `block`, or nothing if it has no table.
+    #[inline]
+    pub fn block_drops(
+        &self,
+        block: usize,
+        context: &LootContext,
+        items: &impl Registry<Item>,
+    ) -> Vec<ItemStack> {
+        self.block_table(block)
+            .map(|table| table.roll(context, items))
+            .unwrap_or_default()
+    }
+}
+
+/// Adds `stack` to `drops`, merging it into an earlier drop of the same item.
+fn push_drop(drops: &mut Vec<ItemStack>, stack: ItemStack) {
+    match drops.iter_mut().find(|d| d.item == stack.item) {
+        Some(drop) => drop.count = drop.count.saturating_add(stack.count),
+        None => drops.push(stack),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, crate::prelude::*};
+
+    const STONE: usize = 1;
+    const COBBLESTONE: usize = 2;
+    const SHEARS: usize = 3;
+
+    fn items() -> DefaultedRegistry<Item> {
+        let mut items = Registrar::<Item>::isolated();
+
+        for key in ["stone", "cobblestone", "shears"] {
+            items
+                .register(AssetLocation::parse(key), Item::new().build())
+                .unwrap();
+        }
+
+        let mut tags = Tags::default();
+        tags.insert(AssetLocation::parse("shears"), [SHEARS]);
+        items.bind_tags(tags);
+
+        items
+    }
+
+    fn pool(conditions: Vec<LootCondition>, entries: Vec<LootEntry>) -> LootPool {
+        LootPool {
+            rolls: CountRange::ONE,
+            conditions: conditions.into_boxed_slice(),
+            entries: entries.into_boxed_slice(),
+        }
+    }
+
+    fn entry(item: usize, weight: u32) -> LootEntry {
+        LootEntry {
+            item: Some(item),
+            weight,
+            count: CountRange::ONE,
+        }
+    }
+
+    fn context(tool: Option<usize>, seed: u64) -> LootContext {
+        LootContext { tool, seed }
+    }
+
+    #[test]
+    fn same_seed_gives_same_drops() {
+        let items = items();
+        let table = LootTable {
+            pools: Box::new([LootPool {
+                rolls: CountRange { min: 1, max: 4 },
+                conditions: Box::new([LootCondition::RandomChance(0.5)]),
+                entries: Box::new([entry(STONE, 1), entry(COBBLESTONE, 1)]),
+            }]),
+        };
+
+        for seed in 0..64 {
+            assert_eq!(
+                table.roll(&context(None, seed), &items),
+                table.roll(&context(None, seed), &items)
+            );
+        }
+    }
+
+    #[test]
+    fn picks_entries_by_weight() {
+        let items = items();
+        let table = LootTable {
+            pools: Box::new([pool(
+                Vec::new(),
+                vec![entry(STONE, 3), entry(COBBLESTONE, 1), entry(SHEARS, 0)],
+            )]),
+        };
+
+        let mut counts = [0usize; 4];
+
+        for seed in 0..4000 {
+            for drop in table.roll(&context(None, seed), &items) {
+                counts[drop.item] += drop.count as usize;
+            }
+        }
+
+        assert_eq!(counts[STONE] + counts[COBBLESTONE], 4000);
+        assert_eq!(counts[SHEARS], 0);
+        assert!((2500..3500).contains(&counts[STONE]));
+    }
+
+    #[test]
+    fn huge_weights_do_not_overflow() {
+        let items = items();
+        let table = LootTable {
+            pools: Box::new([pool(
+                Vec::new(),
+                vec![entry(STONE, u32::MAX), entry(COBBLESTONE, u32::MAX)],
+            )]),
+        };
+
+        assert_eq!(table.roll(&context(None, 0), &items).len(), 1);
+    }
+
+    #[test]
+    fn count_ranges_stay_within_bounds() {
+        let mut rng = Rng::with_seed(0);
+        let range = CountRange { min: 2, max: 5 };
+        let mut seen = [false; 6];
+
+        for _ in 0..1000 {
+            let count = range.roll(&mut rng);
+
+            assert!((2..=5).contains(&count));
+            seen[count as usize] = true;
+        }
+
+        assert_eq!(seen, [false, false, true, true, true, true]);
+        assert_eq!(CountRange::exactly(3).roll(&mut rng), 3);
+        assert_eq!(CountRange { min: 4, max: 1 }.roll(&mut rng), 4);
+    }
+
+    #[test]
+    fn tool_conditions_check_the_tool_tag() {
+        let items = items();
+        let shears = TagKey::<Item>::parse("#shears");
+        let table = LootTable {
+            pools: Box::new([
+                pool(
+                    vec![LootCondition::MatchTool(shears.clone())],
+                    vec![entry(STONE, 1)],
+                ),
+                pool(
+                    vec![LootCondition::NotTool(shears)],
+                    vec![entry(COBBLESTONE, 1)],
+                ),
+            ]),
+        };
+
+        let drops = |tool| table.roll(&context(tool, 0), &items);
+
+        assert_eq!(drops(Some(SHEARS)), [ItemStack::new(STONE, 1)]);
+        assert_eq!(drops(Some(STONE)), [ItemStack::new(COBBLESTONE, 1)]);
+        assert_eq!(drops(None), [ItemStack::new(COBBLESTONE, 1)]);
+    }
+}
@@ -2,18 +2,22 @@ use {
     bitvec::{field::BitField, vec::BitVec},
     hashbrown::{Equivalent, HashTable},
     rapidhash::fast::RandomState,
+    serde::{de, Deserialize, Deserializer, Serialize, Serializer},
     std::{
         cmp::Ordering,
         fmt::{Debug, Formatter},
         hash::{BuildHasher, Hash, Hasher},
         mem::{transmute, ManuallyDrop},
         num::NonZeroUsize,
-        ops::Neg,
+        ops::{Neg, Range},
     },
+    thiserror::Error,
 };
 
 const SENTINEL: usize = usize::MAX;
 
+const WORD_BITS: usize = usize::BITS as usize;
+
 pub struct PatternContainer<T, const N: usize, S = RandomState> {
     hasher: S,
     entries: HashTable<Pattern>,
@@ -21,6 +25,42 @@ pub struct PatternContainer<T, const N: usize, S = RandomState> {
     next_free: Option<usize>,
     container: BitVec,
     bit_cap: BitCapacity,
+    layout: PackingLayout,
+}
+
+/// How the entries of a [`PatternContainer`] are laid out in the words of its container.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum PackingLayout {
+    /// Entries back to back, so an entry may straddle two words. Takes the least memory.
+    #[default]
+    Packed,
+    /// As many whole entries per word as fit, leaving the remaining bits of the word unused, so
+    /// an entry is always read and written within a single word.
+    Aligned,
+}
+
+impl PackingLayout {
+    /// First bit of the entry at `index`.
+    #[inline(always)]
+    const fn start(self, index: usize, bit_len: usize) -> usize {
+        match self {
+            Self::Packed => index * bit_len,
+            Self::Aligned => {
+                let per_word = WORD_BITS / bit_len;
+
+                (index / per_word) * WORD_BITS + (index % per_word) * bit_len
+            }
+        }
+    }
+
+    /// Bits taken by `len` entries.
+    #[inline(always)]
+    const fn bits(self, len: usize, bit_len: usize) -> usize {
+        match self {
+            Self::Packed => len * bit_len,
+            Self::Aligned => len.div_ceil(WORD_BITS / bit_len) * WORD_BITS,
+        }
+    }
 }
 
 impl<T, const N: usize, S> Debug for PatternContainer<T, N, S>
@@ -33,6 +73,7 @@ where
             .field("hasher", &self.hasher)
             .field("patterns", &self.entries)
             .field("bit_cap", &self.bit_cap)
+            .field("layout", &self.layout)
             .finish()
     }
 }
@@ -51,6 +92,7 @@ where
             next_free: self.next_free,
             container: self.container.clone(),
             bit_cap: self.bit_cap,
+            layout: self.layout,
         }
     }
 }
@@ -78,6 +120,27 @@ where
     pub fn with_bit_cap_and_capacity(initial: T, bit_cap: usize, capacity: usize) -> Self {
         Self::new_with(initial, RandomState::new(), bit_cap, capacity)
     }
+
+    #[inline]
+    pub fn with_layout(initial: T, layout: PackingLayout) -> Self {
+        Self::new_with_layout(initial, RandomState::new(), 1, 1, layout)
+    }
+
+    #[inline]
+    pub fn from_dense(values: &[T; N]) -> Self
+    where
+        T: Clone,
+    {
+        Self::from_dense_with(values, RandomState::new())
+    }
+
+    #[inline]
+    pub fn from_dense_with_layout(values: &[T; N], layout: PackingLayout) -> Self
+    where
+        T: Clone,
+    {
+        Self::from_dense_with_hasher_and_layout(values, RandomState::new(), layout)
+    }
 }
 
 impl<T, const N: usize, S> PatternContainer<T, N, S>
@@ -87,6 +150,16 @@ where
 {
     #[inline]
     pub fn new_with(initial: T, hasher: S, bit_cap: usize, capacity: usize) -> Self {
+        Self::new_with_layout(initial, hasher, bit_cap, capacity, PackingLayout::Packed)
+    }
+
+    pub fn new_with_layout(
+        initial: T,
+        hasher: S,
+        bit_cap: usize,
+        capacity: usize,
+        layout: PackingLayout,
+    ) -> Self {
         let mut entries: HashTable<Pattern> = HashTable::with_capacity(capacity);
         let mut patterns: Vec<Slot<T>> = Vec::with_capacity(capacity);
 
@@ -108,18 +181,88 @@ where
             entries,
             patterns,
             next_free: None,
-            container: BitVec::repeat(false, N * bit_cap),
+            container: BitVec::repeat(false, layout.bits(N, bit_cap)),
             bit_cap: BitCapacity::new(bit_cap).unwrap(),
+            layout,
         }
     }
 
+    /// Builds a container holding `values`, with the smallest bit capacity fitting its distinct
+    /// values.
+    ///
+    /// The palette is collected in a single pass over `values`, then the indices are packed
+    /// straight into the words of the container.
+    #[inline]
+    pub fn from_dense_with(values: &[T; N], hasher: S) -> Self
+    where
+        T: Clone,
+    {
+        Self::from_dense_with_hasher_and_layout(values, hasher, PackingLayout::Packed)
+    }
+
+    /// Same as [`Self::from_dense_with`], with the indices laid out as `layout`.
+    pub fn from_dense_with_hasher_and_layout(
+        values: &[T; N],
+        hasher: S,
+        layout: PackingLayout,
+    ) -> Self
+    where
+        T: Clone,
+    {
+        let mut entries: HashTable<Pattern> = HashTable::new();
+        let mut patterns: Vec<Slot<T>> = Vec::new();
+        let mut indices: Vec<usize> = Vec::with_capacity(N);
+
+        for value in values {
+            let hash = make_hash(&hasher, value);
+
+            let pattern = match entries.find_mut(hash, equivalent_pattern(&patterns, value)) {
+                Some(pattern) => {
+                    pattern.counts += 1;
+
+                    pattern.index
+                }
+                None => {
+                    let index = patterns.len();
+
+                    patterns.push(Slot {
+                        value: ManuallyDrop::new(value.clone()),
+                    });
+
+                    entries.insert_unique(
+                        hash,
+                        Pattern { index, counts: 1 },
+                        make_hash_from_pattern(&hasher, &patterns),
+                    );
+
+                    index
+                }
+            };
+
+            indices.push(pattern);
+        }
+
+        let bit_cap = BitCapacity::for_count(patterns.len());
+
+        Self {
+            hasher,
+            entries,
+            patterns,
+            next_free: None,
+            container: pack_indices(N, bit_cap.get(), layout, |index| indices[index]),
+            bit_cap,
+            layout,
+        }
+    }
+
+    /// Entry at `index`, or `None` if `index` is `N` or greater.
     #[inline]
     pub fn get(&self, index: usize) -> Option<&T> {
-        let bit_len = self.bit_cap.get();
-        let start = index * bit_len;
-        let end = start + bit_len;
+        if index >= N {
+            return None;
+        }
 
-        let pattern = self.container[start..end].load::<usize>();
+        let pattern = self.pattern_at(index);
 
         self.patterns
             .get(pattern)
@@ -132,11 +275,7 @@ where
 
         let pattern = self.get_or_insert_pattern(value);
 
-        let bit_len = self.bit_cap.get();
-        let start = index * bit_len;
-        let end = start + bit_len;
-
-        self.container[start..end].store(pattern);
+        self.store_pattern(index, pattern);
     }
 
     #[inline]
@@ -150,21 +289,197 @@ where
         self.add_pattern(value)
     }
 
+    /// Sets every entry in `range` to `value`.
+    ///
+    /// # Panics
+    /// Panics if `range` ends past `N`, before anything is written.
+    #[inline]
+    pub fn fill_range(&mut self, range: Range<usize>, value: T) {
+        self.fill_indices(range, value);
+    }
+
+    /// Sets the entry at every index of `indices` to `value`.
+    ///
+    /// # Panics
+    /// Panics if any index is `N` or greater, before anything is written.
+    pub fn fill_indices(&mut self, indices: impl IntoIterator<Item = usize>, value: T) {
+        let mut indices: Vec<usize> = indices.into_iter().collect();
+
+        indices.sort_unstable();
+        indices.dedup();
+
+        self.write_groups(vec![(value, indices)]);
+    }
+
+    /// Sets the entry at each index to its value, the last one winning for repeated indices.
+    ///
+    /// Unlike calling [`Self::set`] for every entry, the count of each distinct value is updated
+    /// once and the bit capacity grows at most once for the whole batch.
+    ///
+    /// # Panics
+    /// Panics if any index is `N` or greater, before anything is written.
+    pub fn set_many(&mut self, values: impl IntoIterator<Item = (usize, T)>) {
+        let mut values: Vec<(usize, T)> = values.into_iter().collect();
+
+        // The sort is stable, so sorting the reversed batch puts the last value of every index
+        // first.
+        values.reverse();
+        values.sort_by_key(|(index, _)| *index);
+        values.dedup_by_key(|(index, _)| *index);
+
+        let mut groups: Vec<(T, Vec<usize>)> = Vec::new();
+        let mut lookup: HashTable<usize> = HashTable::new();
+
+        for (index, value) in values {
+            let hash = make_hash(&self.hasher, &value);
+
+            if let Some(&group) = lookup.find(hash, |&group| groups[group].0 == value) {
+                groups[group].1.push(index);
+
+                continue;
+            }
+
+            lookup.insert_unique(hash, groups.len(), |&group| {
+                make_hash(&self.hasher, &groups[group].0)
+            });
+            groups.push((value, vec![index]));
+        }
+
+        self.write_groups(groups);
+    }
+
+    /// Copies the entries of `other` in `src` to the entries starting at `dst_offset`.
+    ///
+    /// # Panics
+    /// Panics if `src` or the entries it is copied to end past `N`, before anything is written.
+    #[inline]
+    pub fn copy_from(&mut self, other: &Self, src: Range<usize>, dst_offset: usize)
+    where
+        T: Clone,
+    {
+        let start = src.start;
+
+        self.copy_indices_from(other, src.map(|index| (index, index - start + dst_offset)));
+    }
+
+    /// Copies the entry of `other` at each source index to its destination index, the last pair
+    /// winning for repeated destinations. Every distinct value copied is cloned once.
+    ///
+    /// # Panics
+    /// Panics if any source or destination index is `N` or greater, before anything is written.
+    pub fn copy_indices_from(
+        &mut self,
+        other: &Self,
+        pairs: impl IntoIterator<Item = (usize, usize)>,
+    ) where
+        T: Clone,
+    {
+        let mut pairs: Vec<(usize, usize)> = pairs.into_iter().collect();
+
+        pairs.reverse();
+        pairs.sort_by_key(|&(_, dst)| dst);
+        pairs.dedup_by_key(|(_, dst)| *dst);
+
+        let mut by_pattern: Vec<Vec<usize>> = vec![Vec::new(); other.patterns.len()];
+
+        for (src, dst) in pairs {
+            assert!(src < N, "source index {src} out of bounds for length {N}");
+
+            by_pattern[other.pattern_at(src)].push(dst);
+        }
+
+        let groups = by_pattern
+            .into_iter()
+            .enumerate()
+            .filter(|(_, indices)| !indices.is_empty())
+            .map(|(pattern, indices)| {
+                let value: &T = unsafe { &other.patterns[pattern].value };
+
+                (value.clone(), indices)
+            })
+            .collect();
+
+        self.write_groups(groups);
+    }
+
+    /// Writes the value of every group to its indices. An index may only appear once across all
+    /// groups.
+    ///
+    /// Every index is checked against `N` before anything is written, so an out of bounds index
+    /// never leaves the counts half updated.
+    fn write_groups(&mut self, groups: Vec<(T, Vec<usize>)>) {
+        let mut released = vec![0usize; self.patterns.len()];
+
+        for (_, indices) in &groups {
+            for &index in indices {
+                assert!(index < N, "index {index} out of bounds for length {N}");
+
+                released[self.pattern_at(index)] += 1;
+            }
+        }
+
+        let mut writes = Vec::with_capacity(groups.len());
+        let mut missing = Vec::new();
+
+        // Values already stored are counted before the overwritten entries are released, so
+        // they are never freed and inserted again.
+        for (value, indices) in groups {
+            if indices.is_empty() {
+                continue;
+            }
+
+            match self.get_pattern_mut(&value) {
+                Some(pattern) => {
+                    pattern.counts += indices.len();
+
+                    writes.push((pattern.index, indices));
+                }
+                None => missing.push((value, indices)),
+            }
+        }
+
+        for (pattern, amount) in released.into_iter().enumerate() {
+            if amount > 0 {
+                self.release_pattern(pattern, amount);
+            }
+        }
+
+        let mut highest = 0;
+
+        for (value, indices) in missing {
+            let pattern = self.insert_pattern(value, indices.len());
+
+            highest = highest.max(pattern);
+            writes.push((pattern, indices));
+        }
+
+        self.fit_bit_capacity(highest);
+
+        for (pattern, indices) in writes {
+            for index in indices {
+                self.store_pattern(index, pattern);
+            }
+        }
+    }
+
     #[inline]
     fn decay_pattern_at_index(&mut self, index: usize) {
-        let bit_len = self.bit_cap.get();
-        let start = index * bit_len;
-        let end = start + bit_len;
+        let pattern = self.pattern_at(index);
 
-        let index = self.container[start..end].load::<usize>();
+        self.release_pattern(pattern, 1);
+    }
 
+    /// Takes `amount` entries off the count of the pattern in slot `index`, freeing the slot once
+    /// no entry uses it.
+    #[inline]
+    fn release_pattern(&mut self, index: usize, amount: usize) {
         let pattern: &T = unsafe { &self.patterns[index].value };
 
         if let Ok(mut occupied) = self.entries.find_entry(
             make_hash(&self.hasher, pattern),
             equivalent_pattern(&self.patterns, pattern),
         ) {
-            occupied.get_mut().counts -= 1;
+            occupied.get_mut().counts -= amount;
 
             if occupied.get().counts == 0 {
                 let (removed, _) = occupied.remove();
@@ -184,6 +499,16 @@ where
 
     #[inline]
     fn add_pattern(&mut self, value: T) -> usize {
+        let pattern = self.insert_pattern(value, 1);
+
+        self.fit_bit_capacity(pattern);
+
+        pattern
+    }
+
+    /// Stores `value` in a free slot or a new one, without growing the bit capacity to fit it.
+    #[inline]
+    fn insert_pattern(&mut self, value: T, counts: usize) -> usize {
         let hash = make_hash(&self.hasher, &value);
 
         let value = ManuallyDrop::new(value);
@@ -195,12 +520,6 @@ where
         } else {
             let next = self.patterns.len();
 
-            if required_bits(next) > self.bit_cap.get() {
-                let amount = required_bits(next) - self.bit_cap.get();
-
-                self.grow_bit_capacity(amount);
-            }
-
             self.patterns.push(Slot { value });
 
             next
@@ -210,7 +529,7 @@ where
             hash,
             Pattern {
                 index: pattern,
-                counts: 1,
+                counts,
             },
             make_hash_from_pattern(&self.hasher, &self.patterns),
         );
@@ -254,7 +573,22 @@ impl<T, const N: usize, S> PatternContainer<T, N, S> {
         PatternIter {
             container: &self,
             index: 0,
-            cursor: 0,
+        }
+    }
+
+    /// Writes every entry into `out`, reading the packed indices straight from the words of the
+    /// container.
+    pub fn unpack_into(&self, out: &mut [T; N])
+    where
+        T: Clone,
+    {
+        let words = self.container.as_raw_slice();
+        let bit_len = self.bit_cap.get();
+
+        for (index, slot) in out.iter_mut().enumerate() {
+            let pattern = load_index(words, self.layout.start(index, bit_len), bit_len);
+
+            *slot = T::clone(unsafe { &self.patterns[pattern].value });
         }
     }
 
@@ -268,17 +602,22 @@ impl<T, const N: usize, S> PatternContainer<T, N, S> {
             return false;
         }
 
+        let mut live: Vec<usize> = self.entries.iter().map(|p| p.index).collect();
+        live.sort_unstable();
+
         let mut remap = vec![0usize; self.patterns.len()];
-        let mut new_idx = 0usize;
+
+        for (new_idx, &old_idx) in live.iter().enumerate() {
+            remap[old_idx] = new_idx;
+        }
 
         for entry in self.entries.iter_mut() {
-            remap[entry.index] = new_idx;
-            entry.index = new_idx;
-            new_idx += 1;
+            entry.index = remap[entry.index];
         }
 
-        for old_idx in 0..self.patterns.len() {
-            let new_idx = remap[old_idx];
+        // Moving the values in slot order only moves them down, into slots that are free or
+        // already moved out of.
+        for (new_idx, &old_idx) in live.iter().enumerate() {
             if new_idx != old_idx {
                 unsafe {
                     let value = ManuallyDrop::take(&mut self.patterns[old_idx].value);
@@ -291,29 +630,81 @@ impl<T, const N: usize, S> PatternContainer<T, N, S> {
         self.patterns.truncate(active);
         self.next_free = None;
 
-        let src_cap = self.bit_cap.get();
-        let dst_cap = new_cap.get();
+        let container = pack_indices(N, new_cap.get(), self.layout, |index| {
+            remap[self.pattern_at(index)]
+        });
 
-        let mut new_container = BitVec::repeat(false, N * dst_cap);
+        self.container = container;
+        self.bit_cap = new_cap;
 
-        let mut src_next = 0usize;
-        let mut dst_next = 0usize;
+        true
+    }
 
-        for _ in 0..N {
-            let extracted = self.container[src_next..src_next + src_cap].load::<usize>();
+    /// Repacks the entries into `layout`.
+    pub fn set_layout(&mut self, layout: PackingLayout) {
+        if layout == self.layout {
+            return;
+        }
 
-            let actual_idx = remap[extracted];
+        let container = pack_indices(N, self.bit_cap.get(), layout, |index| {
+            self.pattern_at(index)
+        });
+
+        self.container = container;
+        self.layout = layout;
+    }
 
-            new_container[dst_next..dst_next + dst_cap].store(actual_idx);
+    #[inline(always)]
+    pub const fn layout(&self) -> PackingLayout {
+        self.layout
+    }
 
-            src_next += src_cap;
-            dst_next += dst_cap;
+    /// Slot of the entry at `index`. Aligned entries are read straight from their word, packed
+    /// ones through bitvec since they may straddle two words.
+    #[inline(always)]
+    fn pattern_at(&self, index: usize) -> usize {
+        debug_assert!(index < N, "index {index} out of bounds for length {N}");
+
+        let bit_len = self.bit_cap.get();
+        let start = self.layout.start(index, bit_len);
+
+        match self.layout {
+            PackingLayout::Packed => self.container[start..start + bit_len].load_le::<usize>(),
+            PackingLayout::Aligned => {
+                let word = self.container.as_raw_slice()[start / WORD_BITS];
+
+                (word >> (start % WORD_BITS)) & (usize::MAX >> (WORD_BITS - bit_len))
+            }
         }
+    }
 
-        self.container = new_container;
-        self.bit_cap = new_cap;
+    #[inline(always)]
+    fn store_pattern(&mut self, index: usize, pattern: usize) {
+        debug_assert!(index < N, "index {index} out of bounds for length {N}");
 
-        true
+        let bit_len = self.bit_cap.get();
+        let start = self.layout.start(index, bit_len);
+
+        match self.layout {
+            PackingLayout::Packed => self.container[start..start + bit_len].store_le(pattern),
+            PackingLayout::Aligned => {
+                let word = &mut self.container.as_raw_mut_slice()[start / WORD_BITS];
+                let offset = start % WORD_BITS;
+                let mask = (usize::MAX >> (WORD_BITS - bit_len)) << offset;
+
+                *word = (*word & !mask) | (pattern << offset);
+            }
+        }
+    }
+
+    /// Grows the bit capacity so entries can point to the slot `pattern`.
+    #[inline]
+    fn fit_bit_capacity(&mut self, pattern: usize) {
+        let required = required_bits(pattern);
+
+        if required > self.bit_cap.get() {
+            self.grow_bit_capacity(required - self.bit_cap.get());
+        }
     }
 
     #[inline]
@@ -334,24 +725,11 @@ impl<T, const N: usize, S> PatternContainer<T, N, S> {
 
         let new_cap = self.bit_cap.factor_by(factor).unwrap();
 
-        let src_cap = self.bit_cap.get();
-        let dst_cap = new_cap.get();
-
-        let mut new_container = BitVec::repeat(false, N * dst_cap);
-
-        let mut src_next = 0usize;
-        let mut dst_next = 0usize;
-
-        for _ in 0..N {
-            let extracted = self.container[src_next..src_next + src_cap].load::<usize>();
-
-            new_container[dst_next..dst_next + dst_cap].store(extracted);
-
-            src_next += src_cap;
-            dst_next += dst_cap;
-        }
+        let container = pack_indices(N, new_cap.get(), self.layout, |index| {
+            self.pattern_at(index)
+        });
 
-        self.container = new_container;
+        self.container = container;
         self.bit_cap = new_cap;
     }
 
@@ -382,10 +760,222 @@ impl<T, const N: usize, S> PatternContainer<T, N, S> {
     }
 }
 
+/// Serialized form of a [`PatternContainer`]: its distinct values, and the index of every entry
+/// into them packed `bit_capacity` bits each, lowest bits first, into `u64` words.
+///
+/// The data is packed the same way for every [`PackingLayout`].
+#[derive(Serialize, Deserialize)]
+#[serde(rename = "PatternContainer")]
+struct PackedPatterns<P> {
+    bit_capacity: u8,
+    layout: PackingLayout,
+
+    palette: Vec<P>,
+    data: Vec<u64>,
+}
+
+impl<T, const N: usize, S> PatternContainer<T, N, S> {
+    /// Palette in slot order with the free slots left out, so a container without free slots
+    /// keeps the same indices.
+    fn pack(&self) -> PackedPatterns<&T> {
+        let mut live: Vec<usize> = self.entries.iter().map(|p| p.index).collect();
+        live.sort_unstable();
+
+        let mut remap = vec![0usize; self.patterns.len()];
+
+        for (new_idx, &old_idx) in live.iter().enumerate() {
+            remap[old_idx] = new_idx;
+        }
+
+        let bit_len = self.bit_cap.get();
+        let mut data = vec![0u64; packed_words(N, bit_len)];
+
+        for index in 0..N {
+            write_packed(&mut data, index, bit_len, remap[self.pattern_at(index)]);
+        }
+
+        let palette = live
+            .iter()
+            .map(|&idx| unsafe { &self.patterns[idx].value as &T })
+            .collect();
+
+        PackedPatterns {
+            bit_capacity: bit_len as u8,
+            layout: self.layout,
+            palette,
+            data,
+        }
+    }
+}
+
+impl<T, const N: usize, S> PatternContainer<T, N, S>
+where
+    T: Eq + Hash,
+    S: BuildHasher,
+{
+    /// Rebuilds a container from its serialized form, counting the entries of every value.
+    fn unpack(packed: PackedPatterns<T>, hasher: S) -> Result<Self, PackedPatternsError> {
+        let PackedPatterns {
+            bit_capacity,
+            layout,
+            palette,
+            data,
+        } = packed;
+
+        if bit_capacity == 0 || bit_capacity as usize > BitCapacity::MAX {
+            return Err(PackedPatternsError::InvalidBitCapacity(bit_capacity));
+        }
+
+        let bit_cap = BitCapacity::new(bit_capacity as usize).unwrap();
+
+        if palette.is_empty() {
+            return Err(PackedPatternsError::EmptyPalette);
+        }
+
+        if BitCapacity::for_count(palette.len()) > bit_cap {
+            return Err(PackedPatternsError::PaletteTooLarge {
+                len: palette.len(),
+                bit_capacity,
+            });
+        }
+
+        let bit_len = bit_cap.get();
+        let expected = packed_words(N, bit_len);
+
+        if data.len() != expected {
+            return Err(PackedPatternsError::DataLength {
+                expected,
+                found: data.len(),
+            });
+        }
+
+        let mut counts = vec![0usize; palette.len()];
+        let mut indices = Vec::with_capacity(N);
+
+        for index in 0..N {
+            let pattern = read_packed(&data, index, bit_len);
+
+            let Some(count) = counts.get_mut(pattern) else {
+                return Err(PackedPatternsError::IndexOutOfRange {
+                    index,
+                    pattern,
+                    len: palette.len(),
+                });
+            };
+
+            *count += 1;
+            indices.push(pattern);
+        }
+
+        let container = pack_indices(N, bit_len, layout, |index| indices[index]);
+
+        let mut entries: HashTable<Pattern> = HashTable::with_capacity(palette.len());
+        let mut patterns: Vec<Slot<T>> = Vec::with_capacity(palette.len());
+
+        for (index, value) in palette.into_iter().enumerate() {
+            if counts[index] == 0 {
+                return Err(PackedPatternsError::UnusedValue(index));
+            }
+
+            let hash = make_hash(&hasher, &value);
+
+            if entries
+                .find(hash, equivalent_pattern(&patterns, &value))
+                .is_some()
+            {
+                return Err(PackedPatternsError::DuplicateValue(index));
+            }
+
+            patterns.push(Slot {
+                value: ManuallyDrop::new(value),
+            });
+
+            entries.insert_unique(
+                hash,
+                Pattern {
+                    index,
+                    counts: counts[index],
+                },
+                make_hash_from_pattern(&hasher, &patterns),
+            );
+        }
+
+        Ok(Self {
+            hasher,
+            entries,
+            patterns,
+            next_free: None,
+            container,
+            bit_cap,
+            layout,
+        })
+    }
+}
+
+/// Serializes as the palette of distinct values plus the packed index of every entry, keeping
+/// the bit capacity.
+impl<T, const N: usize, S> Serialize for PatternContainer<T, N, S>
+where
+    T: Serialize,
+{
+    #[inline]
+    fn serialize<Z>(&self, serializer: Z) -> Result<Z::Ok, Z::Error>
+    where
+        Z: Serializer,
+    {
+        self.pack().serialize(serializer)
+    }
+}
+
+/// Rejects palettes that do not fit the bit capacity, hold duplicate or unused values, or data
+/// pointing past the palette.
+impl<'de, T, const N: usize, S> Deserialize<'de> for PatternContainer<T, N, S>
+where
+    T: Eq + Hash + Deserialize<'de>,
+    S: BuildHasher + Default,
+{
+    #[inline]
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let packed = PackedPatterns::<T>::deserialize(deserializer)?;
+
+        Self::unpack(packed, S::default()).map_err(de::Error::custom)
+    }
+}
+
+#[derive(Error, Debug)]
+enum PackedPatternsError {
+    #[error("bit capacity {0} is out of range")]
+    InvalidBitCapacity(u8),
+
+    #[error("palette is empty")]
+    EmptyPalette,
+
+    #[error("{len} palette values do not fit in {bit_capacity} bits")]
+    PaletteTooLarge { len: usize, bit_capacity: u8 },
+
+    #[error("expected {expected} data words, found {found}")]
+    DataLength { expected: usize, found: usize },
+
+    #[error("entry {index} points to palette value {pattern} of {len}")]
+    IndexOutOfRange {
+        index: usize,
+        pattern: usize,
+        len: usize,
+    },
+
+    #[error("palette value {0} is not used by any entry")]
+    UnusedValue(usize),
+
+    #[error("palette value {0} is a duplicate")]
+    DuplicateValue(usize),
+}
+
 pub struct PatternIter<'a, T, const N: usize, S = RandomState> {
     container: &'a PatternContainer<T, N, S>,
     index: usize,
-    cursor: usize,
 }
 
 impl<'a, T, const N: usize, S> ExactSizeIterator for PatternIter<'a, T, N, S> {
@@ -405,13 +995,12 @@ impl<'a, T, const N: usize, S> Iterator for PatternIter<'a, T, N, S> {
         }
 
         let bit_cap = self.container.bit_cap.get();
-        let cursor = self.cursor;
-        let tail = cursor + bit_cap;
+        let words = self.container.container.as_raw_slice();
+        let start = self.container.layout.start(self.index, bit_cap);
 
-        let idx = self.container.container[cursor..tail].load::<usize>();
+        let idx = load_index(words, start, bit_cap);
 
         self.index += 1;
-        self.cursor = tail;
 
         unsafe { Some(&self.container.patterns[idx].value) }
     }
@@ -470,6 +1059,92 @@ const fn required_bits(value: usize) -> usize {
     (usize::BITS - value.leading_zeros()) as usize
 }
 
+/// Packs the `len` slot indices returned by `index_at`, `bit_len` bits each, into a new
+/// container laid out as `layout`.
+fn pack_indices(
+    len: usize,
+    bit_len: usize,
+    layout: PackingLayout,
+    index_at: impl Fn(usize) -> usize,
+) -> BitVec {
+    let bits = layout.bits(len, bit_len);
+    let mut words = vec![0usize; bits.div_ceil(WORD_BITS)];
+
+    for index in 0..len {
+        store_index(&mut words, layout.start(index, bit_len), bit_len, index_at(index));
+    }
+
+    let mut container = BitVec::from_vec(words);
+    container.truncate(bits);
+
+    container
+}
+
+/// Index of `bit_len` bits starting at bit `start` of `words`, the raw storage of a [`BitVec`].
+///
+/// Reads whole words with shifts instead of going through bitvec's generic slicing, matching
+/// `load_le` on the same bits.
+#[inline(always)]
+fn load_index(words: &[usize], start: usize, bit_len: usize) -> usize {
+    let (word, offset) = (start / WORD_BITS, start % WORD_BITS);
+
+    let mut value = words[word] >> offset;
+
+    if offset + bit_len > WORD_BITS {
+        value |= words[word + 1] << (WORD_BITS - offset);
+    }
+
+    value & (usize::MAX >> (WORD_BITS - bit_len))
+}
+
+/// Writes `index` into zeroed bits of `words`, the counterpart of [`load_index`].
+#[inline(always)]
+fn store_index(words: &mut [usize], start: usize, bit_len: usize, index: usize) {
+    let (word, offset) = (start / WORD_BITS, start % WORD_BITS);
+
+    words[word] |= index << offset;
+
+    if offset + bit_len > WORD_BITS {
+        words[word + 1] |= index >> (WORD_BITS - offset);
+    }
+}
+
+/// Number of `u64` words holding `len` values of `bit_len` bits each.
+#[inline(always)]
+const fn packed_words(len: usize, bit_len: usize) -> usize {
+    (len * bit_len).div_ceil(u64::BITS as usize)
+}
+
+#[inline(always)]
+fn read_packed(words: &[u64], index: usize, bit_len: usize) -> usize {
+    let start = index * bit_len;
+    let (word, offset) = (start / 64, start % 64);
+
+    let mut value = words[word] >> offset;
+
+    if offset + bit_len > 64 {
+        value |= words[word + 1] << (64 - offset);
+    }
+
+    let mask = u64::MAX >> (64 - bit_len);
+
+    (value & mask) as usize
+}
+
+#[inline(always)]
+fn write_packed(words: &mut [u64], index: usize, bit_len: usize, value: usize) {
+    let start = index * bit_len;
+    let (word, offset) = (start / 64, start % 64);
+
+    let value = value as u64;
+
+    words[word] |= value << offset;
+
+    if offset + bit_len > 64 {
+        words[word + 1] |= value >> (64 - offset);
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
 pub(crate) struct Pattern {
     index: usize,
@@ -598,3 +1273,261 @@ where
         unsafe { self.value.eq(&other.value) }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LEN: usize = 100;
+
+    type Container = PatternContainer<u32, LEN>;
+
+    fn values(container: &PatternContainer<u32, LEN>) -> Vec<u32> {
+        container.iter().copied().collect()
+    }
+
+    /// Packed form of a container of 4 entries, all held in the single word `data`.
+    fn packed(bit_capacity: u8, palette: &[u32], data: u64) -> PackedPatterns<u32> {
+        PackedPatterns {
+            bit_capacity,
+            layout: PackingLayout::Packed,
+            palette: palette.to_vec(),
+            data: vec![data],
+        }
+    }
+
+    fn unpack(
+        packed: PackedPatterns<u32>,
+    ) -> Result<PatternContainer<u32, 4>, PackedPatternsError> {
+        PatternContainer::unpack(packed, RandomState::new())
+    }
+
+    #[test]
+    fn serde_round_trip_keeps_entries_and_layout() {
+        for layout in [PackingLayout::Packed, PackingLayout::Aligned] {
+            let mut container = Container::with_layout(0, layout);
+
+            for index in 0..LEN {
+                container.set(index, (index % 7) as u32 * 3);
+            }
+
+            // Frees the slot of 3, so the palette is written without the gap.
+            container.fill_indices((0..LEN).filter(|index| index % 7 == 1), 0);
+
+            let text = ron::to_string(&container).unwrap();
+            let decoded: Container = ron::from_str(&text).unwrap();
+
+            assert_eq!(values(&decoded), values(&container));
+            assert_eq!(decoded.bit_capacity(), container.bit_capacity());
+            assert_eq!(decoded.active_entries(), container.active_entries());
+            assert_eq!(decoded.layout(), layout);
+        }
+    }
+
+    #[test]
+    fn unpacks_valid_data() {
+        // Entries 0, 1, 1, 0, one bit each.
+        let container = unpack(packed(1, &[7, 9], 0b0110)).unwrap();
+
+        assert_eq!(container.iter().copied().collect::<Vec<_>>(), [7, 9, 9, 7]);
+        assert_eq!(container.active_entries(), 2);
+    }
+
+    #[test]
+    fn rejects_malformed_data() {
+        assert!(matches!(
+            unpack(packed(0, &[7], 0)),
+            Err(PackedPatternsError::InvalidBitCapacity(0))
+        ));
+        assert!(matches!(
+            unpack(packed(65, &[7], 0)),
+            Err(PackedPatternsError::InvalidBitCapacity(65))
+        ));
+        assert!(matches!(
+            unpack(packed(1, &[], 0)),
+            Err(PackedPatternsError::EmptyPalette)
+        ));
+        assert!(matches!(
+            unpack(packed(1, &[7, 9, 11], 0)),
+            Err(PackedPatternsError::PaletteTooLarge { len: 3, .. })
+        ));
+        assert!(matches!(
+            unpack(PackedPatterns {
+                data: vec![0, 0],
+                ..packed(1, &[7], 0)
+            }),
+            Err(PackedPatternsError::DataLength {
+                expected: 1,
+                found: 2
+            })
+        ));
+        // Entries 0, 1, 2, 3, two bits each.
+        assert!(matches!(
+            unpack(packed(2, &[7, 9, 11], 0b11_10_01_00)),
+            Err(PackedPatternsError::IndexOutOfRange {
+                index: 3,
+                pattern: 3,
+                ..
+            })
+        ));
+        // Entries 0, 1, 1, 0, two bits each.
+        assert!(matches!(
+            unpack(packed(2, &[7, 9, 11], 0b00_01_01_00)),
+            Err(PackedPatternsError::UnusedValue(2))
+        ));
+        assert!(matches!(
+            unpack(packed(1, &[7, 7], 0b0110)),
+            Err(PackedPatternsError::DuplicateValue(1))
+        ));
+    }
+
+    #[test]
+    fn deserializing_malformed_data_fails() {
+        let text = "(bit_capacity: 1, layout: Packed, palette: [7, 7], data: [6])";
+
+        assert!(ron::from_str::<PatternContainer<u32, 4>>(text).is_err());
+    }
+
+    #[test]
+    fn bulk_writes_update_counts_once() {
+        let mut container = Container::new(0);
+
+        container.fill_range(10..20, 5);
+        container.set_many([(3, 1), (3, 2), (4, 2), (12, 0)]);
+
+        let expected: Vec<u32> = (0..LEN as u32)
+            .map(|index| match index {
+                3 | 4 => 2,
+                12 => 0,
+                10..20 => 5,
+                _ => 0,
+            })
+            .collect();
+
+        assert_eq!(values(&container), expected);
+        assert_eq!(container.active_entries(), 3);
+
+        container.fill_range(0..LEN, 9);
+
+        assert_eq!(container.as_single(), Some(&9));
+    }
+
+    #[test]
+    fn copies_entries_between_containers() {
+        let mut source = Container::new(0);
+        source.set_many((0..LEN).map(|index| (index, index as u32 % 4)));
+
+        let mut container = Container::new(7);
+        container.copy_from(&source, 0..4, 10);
+        container.copy_indices_from(&source, [(1, 50), (2, 50), (3, 60)]);
+
+        assert_eq!(values(&container)[8..14], [7, 7, 0, 1, 2, 3]);
+        assert_eq!(container.get(50), Some(&2));
+        assert_eq!(container.get(60), Some(&3));
+        assert_eq!(container.active_entries(), 5);
+    }
+
+    #[test]
+    fn out_of_bounds_bulk_writes_panic_before_writing() {
+        let mut container = Container::new(0);
+
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            container.set_many([(1, 5), (LEN, 5)]);
+        }));
+
+        assert!(result.is_err());
+        assert_eq!(values(&container), vec![0; LEN]);
+        assert_eq!(container.active_entries(), 1);
+    }
+
+    #[test]
+    fn from_dense_uses_smallest_bit_capacity() {
+        let dense: [u32; LEN] = std::array::from_fn(|index| index as u32 % 5 * 10);
+
+        let container = Container::from_dense(&dense);
+
+        assert_eq!(container.bit_capacity(), 3);
+        assert_eq!(container.active_entries(), 5);
+
+        let mut out = [0; LEN];
+        container.unpack_into(&mut out);
+
+        assert_eq!(out, dense);
+        assert_eq!(values(&container), dense);
+    }
+
+    #[test]
+    fn try_compress_drops_freed_slots() {
+        let mut container = Container::new(0);
+
+        container.set_many((0..20).map(|index| (index, index as u32)));
+        container.fill_range(2..LEN, 1);
+
+        let expected = values(&container);
+
+        assert!(container.bit_capacity() > 1);
+        assert!(container.try_compress());
+        assert_eq!(container.bit_capacity(), 1);
+        assert_eq!(values(&container), expected);
+        assert!(!container.try_compress());
+
+        container.set(50, 7);
+
+        assert_eq!(container.get(50), Some(&7));
+        assert_eq!(container.get(0), Some(&0));
+        assert_eq!(container.get(1), Some(&1));
+    }
+
+    #[test]
+    fn layouts_hold_the_same_entries() {
+        // 37 distinct values take 6 bits, which leave 4 bits of every aligned word unused.
+        let dense: [u32; LEN] = std::array::from_fn(|index| index as u32 % 37);
+
+        let mut packed = Container::from_dense_with_layout(&dense, PackingLayout::Packed);
+        let mut aligned = Container::from_dense_with_layout(&dense, PackingLayout::Aligned);
+
+        assert_eq!(aligned.layout(), PackingLayout::Aligned);
+        assert_eq!(aligned.bit_capacity(), 6);
+        assert_eq!(values(&packed), values(&aligned));
+
+        let mut out = [0; LEN];
+        aligned.unpack_into(&mut out);
+
+        assert_eq!(out, dense);
+
+        // Growing the bit capacity repacks the entries in the layout of the container.
+        for container in [&mut packed, &mut aligned] {
+            container.set_many((0..LEN).step_by(2).map(|index| (index, 100 + index as u32)));
+        }
+
+        assert!(aligned.bit_capacity() > 6);
+        assert_eq!(aligned.layout(), PackingLayout::Aligned);
+        assert_eq!(values(&packed), values(&aligned));
+
+        let expected = values(&aligned);
+
+        aligned.set_layout(PackingLayout::Packed);
+        assert_eq!(values(&aligned), expected);
+
+        aligned.set_layout(PackingLayout::Aligned);
+        assert_eq!(values(&aligned), expected);
+        assert_eq!(aligned.get(LEN - 1), Some(&(99 % 37)));
+    }
+
+    #[test]
+    fn out_of_range_indices_read_nothing_in_every_layout() {
+        for layout in [PackingLayout::Packed, PackingLayout::Aligned] {
+            // At 3 bits, an aligned word holds 21 entries, so the last word has room for 5 more
+            // entries past the end.
+            let dense: [u32; LEN] = std::array::from_fn(|index| index as u32 % 5 + 1);
+            let container = Container::from_dense_with_layout(&dense, layout);
+
+            assert_eq!(container.bit_capacity(), 3);
+            assert_eq!(container.get(LEN - 1), Some(&5));
+
+            for index in LEN..LEN + 5 {
+                assert_eq!(container.get(index), None);
+            }
+        }
+    }
+}
@@ -0,0 +1,257 @@
+use {
+    crate::{
+        prelude::*,
+        registries::tag::TAG_PREFIX,
+        util::files::{collect_files, read_dir_sorted, relative_asset_path},
+    },
+    bevy::platform::collections::HashMap,
+    serde::Deserialize,
+    std::{
+        fs,
+        path::{Path, PathBuf},
+    },
+    thiserror::Error,
+};
+
+const RECIPES_DIRECTORY: &str = "recipes";
+
+const RECIPE_EXTENSION: &str = "ron";
+
+/// Contents of a `<namespace>/recipes/**/*.ron` file, registered under its path.
+///
+/// Ingredients are item keys such as `"bevycraft:oak_log"`, or item tags such as
+/// `"#bevycraft:logs"`.
+#[derive(Deserialize, Debug, Clone)]
+enum RecipeFile {
+    Shaped {
+        /// Rows of the grid, one character per slot and a space for an empty slot.
+        pattern: Vec<Box<str>>,
+        key: HashMap<char, Box<str>>,
+        result: ResultFile,
+    },
+    Shapeless {
+        ingredients: Vec<Box<str>>,
+        result: ResultFile,
+    },
+}
+
+#[derive(Deserialize, Debug, Clone)]
+#[serde(deny_unknown_fields)]
+struct ResultFile {
+    item: Box<str>,
+
+    #[serde(default = "default_count")]
+    count: u8,
+}
+
+/// Registers a recipe for every `<namespace>/recipes/**/*.ron` file under `assets`.
+///
+/// Returns the number of recipes registered.
+pub fn register_recipes(
+    recipes: &mut impl Registry<Recipe>,
+    items: &impl Registry<Item>,
+    assets: &Path,
+) -> Result<usize, RecipeError> {
+    let mut count = 0;
+
+    for namespace in read_dir_sorted(assets)? {
+        let root = namespace.join(RECIPES_DIRECTORY);
+
+        let Some(namespace) = namespace.file_name().and_then(|n| n.to_str()) else {
+            continue;
+        };
+
+        let mut files = Vec::new();
+        collect_files(&root, RECIPE_EXTENSION, &mut files)?;
+
+        for file in files {
+            let relative = relative_asset_path(&root, &file).unwrap();
+            let location = AssetLocation::try_with_custom_namespace(namespace, &relative)?;
+
+            let bytes = fs::read(&file)?;
+            let parsed = ron::de::from_bytes::<RecipeFile>(&bytes)
+                .map_err(|e| RecipeError::Deserialize(file.clone(), Box::new(e)))?;
+
+            let recipe = resolve_recipe(parsed, &location, items)?;
+
+            recipes
+                .register(location.clone(), recipe)
+                .map_err(|e| RecipeError::Registration(location, e))?;
+            count += 1;
+        }
+    }
+
+    Ok(count)
+}
+
+fn resolve_recipe(
+    file: RecipeFile,
+    location: &AssetLocation,
+    items: &impl Registry<Item>,
+) -> Result<Recipe, RecipeError> {
+    let (shape, result) = match file {
+        RecipeFile::Shaped {
+            pattern,
+            key,
+            result,
+        } => {
+            let rows = trim_pattern(&pattern);
+
+            let width = rows.iter().map(|row| row.len()).max().unwrap_or(0);
+            let height = rows.len();
+
+            if width == 0 {
+                return Err(RecipeError::EmptyPattern(location.clone()));
+            }
+
+            let mut cells = Vec::with_capacity(width * height);
+
+            for row in &rows {
+                for column in 0..width {
+                    let symbol = row.get(column).copied().unwrap_or(' ');
+
+                    if symbol == ' ' {
+                        cells.push(None);
+
+                        continue;
+                    }
+
+                    let Some(ingredient) = key.get(&symbol) else {
+                        return Err(RecipeError::UnknownSymbol {
+                            location: location.clone(),
+                            symbol,
+                        });
+                    };
+
+                    cells.push(Some(resolve_ingredient(ingredient, location, items)?));
+                }
+            }
+
+            let shape = RecipeShape::Shaped {
+                width,
+                height,
+                pattern: cells.into_boxed_slice(),
+            };
+
+            (shape, result)
+        }
+        RecipeFile::Shapeless {
+            ingredients,
+            result,
+        } => {
+            if ingredients.is_empty() {
+                return Err(RecipeError::EmptyPattern(location.clone()));
+            }
+
+            let ingredients = ingredients
+                .iter()
+                .map(|ingredient| resolve_ingredient(ingredient, location, items))
+                .collect::<Result<Box<[_]>, RecipeError>>()?;
+
+            (RecipeShape::Shapeless { ingredients }, result)
+        }
+    };
+
+    let item = AssetLocation::try_parsing(&result.item)?;
+
+    let Some(idx) = items.key_to_idx(&item) else {
+        return Err(RecipeError::UnknownItem {
+            location: location.clone(),
+            item,
+        });
+    };
+
+    Ok(Recipe::new()
+        .shape(shape)
+        .result(ItemStack::new(idx, result.count.max(1)))
+        .build())
+}
+
+/// Parses an item key, or an item tag if it starts with `#`.
+fn resolve_ingredient(
+    ingredient: &str,
+    location: &AssetLocation,
+    items: &impl Registry<Item>,
+) -> Result<Ingredient, RecipeError> {
+    if ingredient.starts_with(TAG_PREFIX) {
+        return Ok(Ingredient::Tag(TagKey::try_parsing(ingredient)?));
+    }
+
+    let item = AssetLocation::try_parsing(ingredient)?;
+
+    match items.key_to_idx(&item) {
+        Some(idx) => Ok(Ingredient::Item(idx)),
+        None => Err(RecipeError::UnknownItem {
+            location: location.clone(),
+            item,
+        }),
+    }
+}
+
+/// Splits the rows of `pattern` into symbols, dropping the empty rows and columns around it.
+fn trim_pattern(pattern: &[Box<str>]) -> Vec<Vec<char>> {
+    let rows: Vec<Vec<char>> = pattern.iter().map(|row| row.chars().collect()).collect();
+
+    let filled = |symbol: &char| *symbol != ' ';
+
+    let Some(first) = rows.iter().position(|row| row.iter().any(filled)) else {
+        return Vec::new();
+    };
+    let last = rows.iter().rposition(|row| row.iter().any(filled)).unwrap();
+
+    let rows = &rows[first..=last];
+
+    let left = rows
+        .iter()
+        .filter_map(|row| row.iter().position(filled))
+        .min()
+        .unwrap_or(0);
+    let right = rows
+        .iter()
+        .filter_map(|row| row.iter().rposition(filled))
+        .max()
+        .unwrap_or(0);
+
+    rows.iter()
+        .map(|row| {
+            (left..=right)
+                .map(|column| row.get(column).copied().unwrap_or(' '))
+                .collect()
+        })
+        .collect()
+}
+
+#[inline(always)]
+const fn default_count() -> u8 {
+    1
+}
+
+#[derive(Error, Debug)]
+pub enum RecipeError {
+    #[error("io error: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("failed to deserialize `{path}`: {error}", path = .0.display(), error = .1)]
+    Deserialize(PathBuf, Box<ron::de::SpannedError>),
+
+    #[error("invalid location: {0}")]
+    InvalidLocation(#[from] AssetLocationError),
+
+    #[error("recipe `{0}` has no ingredients")]
+    EmptyPattern(AssetLocation),
+
+    #[error("recipe `{location}` uses undefined symbol `{symbol}`")]
+    UnknownSymbol {
+        location: AssetLocation,
+        symbol: char,
+    },
+
+    #[error("recipe `{location}` uses unknown item `{item}`")]
+    UnknownItem {
+        location: AssetLocation,
+        item: AssetLocation,
+    },
+
+    #[error("failed to register `{0}`: {1}")]
+    Registration(AssetLocation, RegistrationError),
+}
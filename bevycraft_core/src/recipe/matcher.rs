@@ -0,0 +1,342 @@
+use {
+    crate::prelude::{Ingredient, Item, ItemStack, Recipe, RecipeShape, Registry},
+    bevy::{ecs::resource::Resource, platform::collections::HashMap},
+};
+
+/// The slots of a crafting grid, row by row, `width` slots per row.
+#[derive(Debug, Clone, Copy)]
+pub struct CraftingGrid<'a> {
+    slots: &'a [ItemStack],
+    width: usize,
+}
+
+impl<'a> CraftingGrid<'a> {
+    /// Panics if `slots` does not split into whole rows of `width` slots.
+    #[inline]
+    pub fn new(slots: &'a [ItemStack], width: usize) -> Self {
+        assert!(width > 0 && slots.len().is_multiple_of(width));
+
+        Self { slots, width }
+    }
+
+    #[inline(always)]
+    pub const fn width(&self) -> usize {
+        self.width
+    }
+
+    #[inline(always)]
+    pub const fn height(&self) -> usize {
+        self.slots.len() / self.width
+    }
+
+    #[inline]
+    pub fn get(&self, x: usize, y: usize) -> ItemStack {
+        self.slots[x + y * self.width]
+    }
+
+    /// Items of the non-empty slots.
+    #[inline]
+    pub fn items(&self) -> impl Iterator<Item = usize> + '_ {
+        self.slots
+            .iter()
+            .filter(|stack| !stack.is_empty())
+            .map(|stack| stack.item)
+    }
+
+    /// Smallest `(x, y, width, height)` rectangle holding every non-empty slot.
+    fn bounds(&self) -> Option<(usize, usize, usize, usize)> {
+        let (mut min_x, mut min_y) = (usize::MAX, usize::MAX);
+        let (mut max_x, mut max_y) = (0, 0);
+
+        for (index, stack) in self.slots.iter().enumerate() {
+            if stack.is_empty() {
+                continue;
+            }
+
+            let (x, y) = (index % self.width, index / self.width);
+
+            min_x = min_x.min(x);
+            min_y = min_y.min(y);
+            max_x = max_x.max(x);
+            max_y = max_y.max(y);
+        }
+
+        (min_x != usize::MAX).then(|| (min_x, min_y, max_x - min_x + 1, max_y - min_y + 1))
+    }
+}
+
+/// Finds the recipe matching a crafting grid without testing every recipe.
+///
+/// Every recipe is indexed under the items accepted by its most selective ingredient, so a grid
+/// only tests the recipes indexed under one of its items. Recipes that cannot match anything are
+/// left out.
+#[derive(Resource, Debug, Clone, Default)]
+pub struct RecipeBook {
+    by_item: HashMap<usize, Vec<usize>>,
+}
+
+impl RecipeBook {
+    /// Indexes every recipe of `recipes`. Item tags must be bound already.
+    pub fn build(recipes: &impl Registry<Recipe>, items: &impl Registry<Item>) -> Self {
+        let mut by_item: HashMap<usize, Vec<usize>> = HashMap::new();
+
+        for (idx, _, recipe) in recipes.iter_indexed() {
+            let Some(candidates) = recipe
+                .shape()
+                .ingredients()
+                .map(|ingredient| ingredient.candidates(items))
+                .min_by_key(Vec::len)
+            else {
+                continue;
+            };
+
+            for item in candidates {
+                by_item.entry(item).or_default().push(idx);
+            }
+        }
+
+        Self { by_item }
+    }
+
+    /// Index of the first registered recipe matching `grid`.
+    pub fn find(
+        &self,
+        grid: &CraftingGrid,
+        recipes: &impl Registry<Recipe>,
+        items: &impl Registry<Item>,
+    ) -> Option<usize> {
+        let mut candidates: Vec<usize> = grid
+            .items()
+            .filter_map(|item| self.by_item.get(&item))
+            .flatten()
+            .copied()
+            .collect();
+
+        candidates.sort_unstable();
+        candidates.dedup();
+
+        candidates.into_iter().find(|&idx| {
+            recipes
+                .get_by_idx(idx)
+                .is_some_and(|recipe| matches(recipe.shape(), grid, items))
+        })
+    }
+
+    /// Result of the first registered recipe matching `grid`.
+    #[inline]
+    pub fn output(
+        &self,
+        grid: &CraftingGrid,
+        recipes: &impl Registry<Recipe>,
+        items: &impl Registry<Item>,
+    ) -> Option<ItemStack> {
+        let idx = self.find(grid, recipes, items)?;
+
+        recipes.get_by_idx(idx).map(Recipe::result)
+    }
+}
+
+/// Whether `grid` holds exactly the ingredients of `shape`, laid out as it requires.
+pub fn matches(shape: &RecipeShape, grid: &CraftingGrid, items: &impl Registry<Item>) -> bool {
+    match shape {
+        RecipeShape::Shaped {
+            width,
+            height,
+            pattern,
+        } => {
+            let Some((x, y, w, h)) = grid.bounds() else {
+                return false;
+            };
+
+            if (w, h) != (*width, *height) {
+                return false;
+            }
+
+            [false, true].into_iter().any(|mirrored| {
+                (0..h).all(|py| {
+                    (0..w).all(|px| {
+                        let column = if mirrored { w - 1 - px } else { px };
+                        let stack = grid.get(x + px, y + py);
+
+                        match &pattern[column + py * w] {
+                            Some(ingredient) => {
+                                !stack.is_empty() && ingredient.test(stack.item, items)
+                            }
+                            None => stack.is_empty(),
+                        }
+                    })
+                })
+            })
+        }
+        RecipeShape::Shapeless { ingredients } => {
+            let stacks: Vec<usize> = grid.items().collect();
+
+            stacks.len() == ingredients.len()
+                && assign(ingredients, &stacks, &mut vec![false; stacks.len()], items)
+        }
+    }
+}
+
+/// Whether every ingredient can take a different one of the `stacks`, trying every assignment.
+fn assign(
+    ingredients: &[Ingredient],
+    stacks: &[usize],
+    used: &mut [bool],
+    items: &impl Registry<Item>,
+) -> bool {
+    let Some((ingredient, rest)) = ingredients.split_first() else {
+        return true;
+    };
+
+    for (index, &item) in stacks.iter().enumerate() {
+        if used[index] || !ingredient.test(item, items) {
+            continue;
+        }
+
+        used[index] = true;
+
+        if assign(rest, stacks, used, items) {
+            return true;
+        }
+
+        used[index] = false;
+    }
+
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, crate::prelude::*};
+
+    const PLANKS: usize = 1;
+    const STICK: usize = 2;
+    const OAK_LOG: usize = 3;
+    const BIRCH_LOG: usize = 4;
+
+    fn items() -> DefaultedRegistry<Item> {
+        let mut items = DefaultedRegistry::new(AssetLocation::parse("air"), Item::default());
+
+        for key in ["oak_planks", "stick", "oak_log", "birch_log"] {
+            items
+                .register(AssetLocation::parse(key), Item::default())
+                .unwrap();
+        }
+
+        let mut tags = Tags::default();
+        tags.insert(AssetLocation::parse("logs"), [OAK_LOG, BIRCH_LOG]);
+        items.bind_tags(tags);
+
+        items
+    }
+
+    fn logs() -> Ingredient {
+        Ingredient::Tag(TagKey::parse("logs"))
+    }
+
+    /// Planks along the top row and down the left column of a 2 by 2 pattern.
+    fn corner() -> RecipeShape {
+        let planks = Some(Ingredient::Item(PLANKS));
+
+        RecipeShape::Shaped {
+            width: 2,
+            height: 2,
+            pattern: Box::new([planks.clone(), planks.clone(), planks, None]),
+        }
+    }
+
+    /// A 3 by 3 grid holding a single item in each `(slot, item)` of `filled`.
+    fn grid(filled: &[(usize, usize)]) -> [ItemStack; 9] {
+        let mut slots = [ItemStack::EMPTY; 9];
+
+        for &(slot, item) in filled {
+            slots[slot] = ItemStack::new(item, 1);
+        }
+
+        slots
+    }
+
+    #[test]
+    fn shaped_recipes_match_anywhere_and_mirrored() {
+        let items = items();
+
+        let placed = grid(&[(4, PLANKS), (5, PLANKS), (7, PLANKS)]);
+        let mirrored = grid(&[(4, PLANKS), (5, PLANKS), (8, PLANKS)]);
+        let flipped = grid(&[(4, PLANKS), (7, PLANKS), (8, PLANKS)]);
+        let extra = grid(&[(0, STICK), (4, PLANKS), (5, PLANKS), (7, PLANKS)]);
+
+        assert!(matches(&corner(), &CraftingGrid::new(&placed, 3), &items));
+        assert!(matches(&corner(), &CraftingGrid::new(&mirrored, 3), &items));
+        assert!(!matches(&corner(), &CraftingGrid::new(&flipped, 3), &items));
+        assert!(!matches(&corner(), &CraftingGrid::new(&extra, 3), &items));
+        assert!(!matches(
+            &corner(),
+            &CraftingGrid::new(&grid(&[]), 3),
+            &items
+        ));
+    }
+
+    #[test]
+    fn shapeless_recipes_try_every_assignment() {
+        let items = items();
+
+        let shape = RecipeShape::Shapeless {
+            ingredients: Box::new([logs(), Ingredient::Item(OAK_LOG)]),
+        };
+
+        // The tag takes the oak log first, so the match only holds once it backtracks.
+        let both = grid(&[(0, OAK_LOG), (8, BIRCH_LOG)]);
+        let birch = grid(&[(0, BIRCH_LOG), (8, BIRCH_LOG)]);
+        let extra = grid(&[(0, OAK_LOG), (4, STICK), (8, BIRCH_LOG)]);
+
+        assert!(matches(&shape, &CraftingGrid::new(&both, 3), &items));
+        assert!(!matches(&shape, &CraftingGrid::new(&birch, 3), &items));
+        assert!(!matches(&shape, &CraftingGrid::new(&extra, 3), &items));
+    }
+
+    #[test]
+    fn recipe_book_finds_the_matching_recipe() {
+        let items = items();
+        let mut recipes = OrderedRegistry::new();
+
+        recipes
+            .register(
+                AssetLocation::parse("planks"),
+                Recipe::new()
+                    .shape(RecipeShape::Shapeless {
+                        ingredients: Box::new([logs()]),
+                    })
+                    .result(ItemStack::new(PLANKS, 4))
+                    .build(),
+            )
+            .unwrap();
+        recipes
+            .register(
+                AssetLocation::parse("corner"),
+                Recipe::new()
+                    .shape(corner())
+                    .result(ItemStack::new(STICK, 1))
+                    .build(),
+            )
+            .unwrap();
+
+        let book = RecipeBook::build(&recipes, &items);
+
+        let log = grid(&[(2, BIRCH_LOG)]);
+        let planks = grid(&[(0, PLANKS), (1, PLANKS), (3, PLANKS)]);
+        let stick = grid(&[(2, STICK)]);
+
+        assert_eq!(
+            book.output(&CraftingGrid::new(&log, 3), &recipes, &items),
+            Some(ItemStack::new(PLANKS, 4))
+        );
+        assert_eq!(
+            book.find(&CraftingGrid::new(&planks, 3), &recipes, &items),
+            Some(1)
+        );
+        assert_eq!(
+            book.find(&CraftingGrid::new(&stick, 3), &recipes, &items),
+            None
+        );
+    }
+}
@@ -0,0 +1,84 @@
+use {
+    crate::prelude::{Item, ItemStack, Registry, TagKey},
+    bevycraft_macros::Registrar,
+    builder_pattern::Builder,
+};
+
+/// What a single slot of a recipe accepts.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Ingredient {
+    /// Index into `Registrar::<Item>`.
+    Item(usize),
+    /// Any item in the tag.
+    Tag(TagKey<Item>),
+}
+
+impl Ingredient {
+    #[inline]
+    pub fn test(&self, item: usize, items: &impl Registry<Item>) -> bool {
+        match self {
+            Self::Item(expected) => *expected == item,
+            Self::Tag(tag) => items.is_in_tag(item, tag),
+        }
+    }
+
+    /// Every item accepted, in index order.
+    pub fn candidates(&self, items: &impl Registry<Item>) -> Vec<usize> {
+        match self {
+            Self::Item(item) => vec![*item],
+            Self::Tag(tag) => items.tags().members(tag).collect(),
+        }
+    }
+}
+
+/// How the ingredients of a recipe must be laid out in the crafting grid.
+#[derive(Debug, Clone, PartialEq)]
+pub enum RecipeShape {
+    /// Ingredients in a `width` by `height` grid, row by row, `None` being an empty slot.
+    ///
+    /// The pattern may sit anywhere in the crafting grid and may be mirrored horizontally.
+    Shaped {
+        width: usize,
+        height: usize,
+        pattern: Box<[Option<Ingredient>]>,
+    },
+    /// Ingredients anywhere in the crafting grid, one item each.
+    Shapeless { ingredients: Box<[Ingredient]> },
+}
+
+impl RecipeShape {
+    /// Every ingredient of the recipe, empty slots left out.
+    pub fn ingredients(&self) -> impl Iterator<Item = &Ingredient> {
+        let (pattern, ingredients) = match self {
+            Self::Shaped { pattern, .. } => (&pattern[..], &[][..]),
+            Self::Shapeless { ingredients } => (&[][..], &ingredients[..]),
+        };
+
+        pattern.iter().flatten().chain(ingredients)
+    }
+}
+
+#[derive(Registrar, Builder, Debug, PartialEq)]
+pub struct Recipe {
+    #[into]
+    #[public]
+    #[default(RecipeShape::Shapeless { ingredients: Box::new([]) })]
+    shape: RecipeShape,
+
+    #[into]
+    #[public]
+    #[default(ItemStack::EMPTY)]
+    result: ItemStack,
+}
+
+impl Recipe {
+    #[inline(always)]
+    pub const fn shape(&self) -> &RecipeShape {
+        &self.shape
+    }
+
+    #[inline(always)]
+    pub const fn result(&self) -> ItemStack {
+        self.result
+    }
+}
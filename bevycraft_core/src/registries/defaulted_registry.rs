@@ -1,5 +1,5 @@
 use {
-    crate::prelude::{AssetLocation, Registrable, RegistrationError, Registry},
+    crate::prelude::{AssetLocation, Registrable, RegistrationError, Registry, Tags},
     bevy::{ecs::resource::Resource, platform::collections::HashMap},
     rapidhash::fast::RandomState,
 };
@@ -9,6 +9,7 @@ pub struct DefaultedRegistry<T: Registrable> {
     key_to_idx: HashMap<AssetLocation, usize, RandomState>,
     idx_to_key: Vec<AssetLocation>,
     values: Vec<T>,
+    tags: Tags,
     frozen: bool,
 }
 
@@ -27,6 +28,7 @@ impl<T: Registrable> DefaultedRegistry<T> {
             key_to_idx,
             idx_to_key,
             values,
+            tags: Tags::default(),
             frozen: false,
         }
     }
@@ -65,9 +67,16 @@ impl<T: Registrable> DefaultedRegistry<T> {
 impl<T: Registrable> Registry<T> for DefaultedRegistry<T> {
     #[inline]
     fn iter(&self) -> impl Iterator<Item = (&AssetLocation, &T)> {
-        self.key_to_idx
+        self.idx_to_key.iter().zip(self.values.iter())
+    }
+
+    #[inline]
+    fn iter_indexed(&self) -> impl Iterator<Item = (usize, &AssetLocation, &T)> {
+        self.idx_to_key
             .iter()
-            .map(|(key, &idx)| (key, &self.values[idx]))
+            .zip(self.values.iter())
+            .enumerate()
+            .map(|(idx, (key, value))| (idx, key, value))
     }
 
     #[inline]
@@ -103,6 +112,11 @@ impl<T: Registrable> Registry<T> for DefaultedRegistry<T> {
         self.idx_to_key.get(index)
     }
 
+    #[inline]
+    fn default_idx(&self) -> Option<usize> {
+        Some(0)
+    }
+
     #[inline]
     fn frozen(&self) -> bool {
         self.frozen
@@ -137,8 +151,37 @@ impl<T: Registrable> Registry<T> for DefaultedRegistry<T> {
         Ok(self.values.last().unwrap())
     }
 
+    #[inline]
+    fn register_or_replace(
+        &mut self,
+        location: AssetLocation,
+        value: T,
+    ) -> Result<&T, RegistrationError> {
+        if self.frozen {
+            return Err(RegistrationError::FrozenRegistry);
+        }
+
+        let Some(idx) = self.key_to_idx.get(&location).copied() else {
+            return self.register(location, value);
+        };
+
+        self.values[idx] = value;
+
+        Ok(&self.values[idx])
+    }
+
     #[inline]
     fn freeze(&mut self) {
         self.frozen = true;
     }
+
+    #[inline]
+    fn tags(&self) -> &Tags {
+        &self.tags
+    }
+
+    #[inline]
+    fn bind_tags(&mut self, tags: Tags) {
+        self.tags = tags;
+    }
 }
@@ -33,15 +33,28 @@ impl<'a, T: Registrable> Holder<'a, T> {
         }
     }
 
-    pub fn registrar(&self, registry: &mut impl Registry<T>) {
+    /// Registers this holder's value and binds its id.
+    ///
+    /// Registering into further registries (e.g. isolated ones in tests) is allowed as long as
+    /// the entry lands on the same index as the first time.
+    pub fn registrar(&self, registry: &mut impl Registry<T>) -> Result<(), RegistrationError> {
         let location = AssetLocation::parse(self.key);
 
-        registry
-            .register(location.clone(), (self.factory)())
-            .expect("Registration failed");
+        // Keys already present (e.g. the default entry) keep their index; anything else is
+        // appended, so the bound id must match the index the entry is about to land on.
+        let index = registry.key_to_idx(&location).unwrap_or(registry.len());
+
+        if self.id.get().is_some_and(|&id| id != index) {
+            return Err(RegistrationError::HolderAlreadyRegistered);
+        }
+
+        registry.register(location.clone(), (self.factory)())?;
 
         let id = registry.key_to_idx(&location).unwrap();
-        self.id.set(id).unwrap();
+
+        self.id.get_or_init(|| id);
+
+        Ok(())
     }
 
     #[inline]
@@ -56,3 +69,21 @@ impl<'a, T: Registrable> Holder<'a, T> {
         unsafe { transmute(guard) }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use {crate::blocks::*, crate::prelude::*};
+
+    #[test]
+    fn registers_into_several_isolated_registries() {
+        let mut first = Registrar::<Block>::isolated();
+        let mut second = Registrar::<Block>::isolated();
+
+        register_blocks(&mut first).unwrap();
+        register_blocks(&mut second).unwrap();
+
+        assert_eq!(first.len(), second.len());
+        assert_eq!(*AIR, 0);
+        assert_eq!(second.key_to_idx(&AssetLocation::parse("dirt")), Some(*DIRT));
+    }
+}
@@ -2,5 +2,8 @@ pub mod asset_location;
 pub mod defaulted_registry;
 pub mod holder;
 pub mod ordered_registry;
+pub mod plugin;
 pub mod registrar;
 pub mod registry;
+pub mod snapshot;
+pub mod tag;
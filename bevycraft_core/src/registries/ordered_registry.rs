@@ -1,6 +1,6 @@
 use {
     crate::{
-        prelude::{AssetLocation, Registrable, RegistrationError},
+        prelude::{AssetLocation, Registrable, RegistrationError, Tags},
         registries::registry::Registry,
     },
     bevy::{ecs::resource::Resource, platform::collections::HashMap},
@@ -12,6 +12,7 @@ pub struct OrderedRegistry<T: Registrable> {
     key_to_idx: HashMap<AssetLocation, usize, RandomState>,
     idx_to_key: Vec<AssetLocation>,
     values: Vec<T>,
+    tags: Tags,
 
     frozen: bool,
 }
@@ -23,6 +24,7 @@ impl<T: Registrable> OrderedRegistry<T> {
             key_to_idx: HashMap::with_hasher(RandomState::new()),
             idx_to_key: Vec::new(),
             values: Vec::new(),
+            tags: Tags::default(),
             frozen: false,
         }
     }
@@ -31,14 +33,21 @@ impl<T: Registrable> OrderedRegistry<T> {
 impl<T: Registrable> Registry<T> for OrderedRegistry<T> {
     #[inline]
     fn iter(&self) -> impl Iterator<Item = (&AssetLocation, &T)> {
-        self.key_to_idx
+        self.idx_to_key.iter().zip(self.values.iter())
+    }
+
+    #[inline]
+    fn iter_indexed(&self) -> impl Iterator<Item = (usize, &AssetLocation, &T)> {
+        self.idx_to_key
             .iter()
-            .map(|(key, &idx)| (key, &self.values[idx]))
+            .zip(self.values.iter())
+            .enumerate()
+            .map(|(idx, (key, value))| (idx, key, value))
     }
 
     #[inline]
     fn keys(&self) -> impl Iterator<Item = &AssetLocation> {
-        self.key_to_idx.keys()
+        self.idx_to_key.iter()
     }
 
     #[inline]
@@ -69,6 +78,11 @@ impl<T: Registrable> Registry<T> for OrderedRegistry<T> {
         self.idx_to_key.get(index)
     }
 
+    #[inline]
+    fn default_idx(&self) -> Option<usize> {
+        None
+    }
+
     #[inline]
     fn frozen(&self) -> bool {
         self.frozen
@@ -97,8 +111,37 @@ impl<T: Registrable> Registry<T> for OrderedRegistry<T> {
         Ok(self.values.last().unwrap())
     }
 
+    #[inline]
+    fn register_or_replace(
+        &mut self,
+        location: AssetLocation,
+        value: T,
+    ) -> Result<&T, RegistrationError> {
+        if self.frozen {
+            return Err(RegistrationError::FrozenRegistry);
+        }
+
+        let Some(idx) = self.key_to_idx.get(&location).copied() else {
+            return self.register(location, value);
+        };
+
+        self.values[idx] = value;
+
+        Ok(&self.values[idx])
+    }
+
     #[inline]
     fn freeze(&mut self) {
         self.frozen = true;
     }
+
+    #[inline]
+    fn tags(&self) -> &Tags {
+        &self.tags
+    }
+
+    #[inline]
+    fn bind_tags(&mut self, tags: Tags) {
+        self.tags = tags;
+    }
 }
@@ -0,0 +1,180 @@
+use {
+    crate::prelude::{
+        Block, BlockStates, Item, Registrable, Registrar, RegistrarOps, RegistrationError,
+    },
+    bevy::{
+        app::{App, MainScheduleOrder, Plugin},
+        ecs::{
+            resource::Resource,
+            schedule::{IntoScheduleConfigs, ScheduleLabel, SystemSet},
+            system::ResMut,
+        },
+        log::info,
+    },
+};
+
+/// The concrete registry type behind `Registrar::<T>`.
+pub type RegistryOf<T> = <Registrar<T> as RegistrarOps<T>>::Registry;
+
+type Registration = Box<dyn FnOnce() -> Result<(), RegistrationError> + Send + Sync>;
+
+/// Startup schedule in which registries are filled and frozen.
+///
+/// It runs before every other startup schedule, including the initial state transition, so
+/// `OnEnter` systems of the starting state already see complete, frozen registries.
+#[derive(ScheduleLabel, Debug, Clone, Eq, PartialEq, Hash)]
+pub struct RegistryStartup;
+
+#[derive(SystemSet, Debug, Clone, Eq, PartialEq, Hash)]
+pub enum RegistrySet {
+    /// Runs the registrations queued through [`RegistryAppExt`], in call order.
+    Register,
+    /// Adds or replaces entries from data files, or derived from other registries.
+    Load,
+    /// Attaches data derived from the entries, e.g. tags.
+    Bind,
+    /// Freezes every registry added to the [`RegistryPlugin`], then builds [`BlockStates`].
+    Freeze,
+}
+
+#[derive(Default)]
+pub struct RegistryPlugin {
+    lockers: Vec<fn()>,
+}
+
+impl RegistryPlugin {
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Freezes the registry of `T` at the end of [`RegistryStartup`].
+    #[inline]
+    pub fn with<T: Registrable>(mut self) -> Self
+    where
+        Registrar<T>: RegistrarOps<T>,
+    {
+        self.lockers.push(Registrar::<T>::lock_registry);
+        self
+    }
+}
+
+impl Plugin for RegistryPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_schedule(RegistryStartup);
+
+        app.world_mut()
+            .resource_mut::<MainScheduleOrder>()
+            .startup_labels
+            .insert(0, RegistryStartup.intern());
+
+        let lockers = self.lockers.clone();
+
+        app.init_resource::<PendingRegistrations>()
+            .configure_sets(
+                RegistryStartup,
+                (
+                    RegistrySet::Register,
+                    RegistrySet::Load,
+                    RegistrySet::Bind,
+                    RegistrySet::Freeze,
+                )
+                    .chain(),
+            )
+            .add_systems(
+                RegistryStartup,
+                (
+                    run_registrations.in_set(RegistrySet::Register),
+                    (
+                        move || {
+                            lockers.iter().for_each(|lock| lock());
+
+                            info!("Froze {} registries", lockers.len());
+                        },
+                        build_block_states,
+                    )
+                        .chain()
+                        .in_set(RegistrySet::Freeze),
+                ),
+            );
+    }
+}
+
+/// Registrations queued by [`RegistryAppExt`], waiting for [`RegistryStartup`].
+#[derive(Resource, Default)]
+struct PendingRegistrations(Vec<Registration>);
+
+fn build_block_states() {
+    let states = BlockStates::build_global();
+
+    info!("Built {} block states", states.len());
+}
+
+fn run_registrations(mut pending: ResMut<PendingRegistrations>) {
+    for registration in pending.0.drain(..) {
+        if let Err(e) = registration() {
+            panic!("Registration failed: {}", e);
+        }
+    }
+}
+
+pub trait RegistryAppExt {
+    /// Queues `registration` to run against the global registry of `T` during
+    /// [`RegistrySet::Register`]. Queued registrations run in call order, so ids only depend
+    /// on the order plugins are added in.
+    fn register_entries<T: Registrable>(
+        &mut self,
+        registration: impl FnOnce(&mut RegistryOf<T>) -> Result<(), RegistrationError>
+        + Send
+        + Sync
+        + 'static,
+    ) -> &mut Self
+    where
+        Registrar<T>: RegistrarOps<T>;
+
+    #[inline]
+    fn register_blocks(
+        &mut self,
+        registration: impl FnOnce(&mut RegistryOf<Block>) -> Result<(), RegistrationError>
+        + Send
+        + Sync
+        + 'static,
+    ) -> &mut Self {
+        self.register_entries::<Block>(registration)
+    }
+
+    #[inline]
+    fn register_items(
+        &mut self,
+        registration: impl FnOnce(&mut RegistryOf<Item>) -> Result<(), RegistrationError>
+        + Send
+        + Sync
+        + 'static,
+    ) -> &mut Self {
+        self.register_entries::<Item>(registration)
+    }
+}
+
+impl RegistryAppExt for App {
+    fn register_entries<T: Registrable>(
+        &mut self,
+        registration: impl FnOnce(&mut RegistryOf<T>) -> Result<(), RegistrationError>
+        + Send
+        + Sync
+        + 'static,
+    ) -> &mut Self
+    where
+        Registrar<T>: RegistrarOps<T>,
+    {
+        self.world_mut()
+            .get_resource_or_init::<PendingRegistrations>()
+            .0
+            .push(Box::new(move || {
+                let mut registry = Registrar::<T>::write_to_registry()?;
+
+                registration(&mut *registry)
+            }));
+
+        self
+    }
+}
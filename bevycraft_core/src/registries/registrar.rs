@@ -5,9 +5,21 @@ use {
 };
 
 pub trait RegistrarOps<T: Registrable> {
-    fn read_from_registry<'a>() -> RwLockReadGuard<'a, impl Registry<T>>;
+    type Registry: Registry<T>;
 
-    fn write_to_registry<'a>() -> RwLockWriteGuard<'a, impl Registry<T>>;
+    fn read_from_registry<'a>() -> RwLockReadGuard<'a, Self::Registry>;
+
+    /// Fails with [`RegistrationError::FrozenRegistry`] once the registry has been locked.
+    fn write_to_registry<'a>() -> Result<RwLockWriteGuard<'a, Self::Registry>, RegistrationError>;
+
+    /// Creates an empty registry of the same kind as the global one, detached from it.
+    /// Useful to register entries without touching global state, e.g. in tests.
+    fn isolated() -> Self::Registry;
+
+    /// Freezes the registry and rejects every later call to [`Self::write_to_registry`].
+    fn lock_registry();
+
+    fn is_locked() -> bool;
 }
 
 pub struct Registrar<T: Registrable>(PhantomData<T>);
@@ -1,5 +1,5 @@
 use {
-    crate::prelude::AssetLocation,
+    crate::prelude::{AssetLocation, TagKey, Tags},
     std::{
         any::TypeId,
         fmt::{Debug, Formatter},
@@ -12,8 +12,13 @@ pub trait Registry<T>: Send + Sync + 'static
 where
     T: Registrable,
 {
+    /// Iterates entries in index order.
     fn iter(&self) -> impl Iterator<Item = (&AssetLocation, &T)>;
 
+    /// Iterates entries in index order, along with their index.
+    fn iter_indexed(&self) -> impl Iterator<Item = (usize, &AssetLocation, &T)>;
+
+    /// Iterates keys in index order.
     fn keys(&self) -> impl Iterator<Item = &AssetLocation>;
 
     fn contains_key(&self, location: &AssetLocation) -> bool;
@@ -26,13 +31,35 @@ where
 
     fn idx_to_key(&self, index: usize) -> Option<&AssetLocation>;
 
+    /// Index of the entry used in place of missing keys, if the registry has one.
+    fn default_idx(&self) -> Option<usize>;
+
     fn frozen(&self) -> bool;
 
     fn len(&self) -> usize;
 
     fn register(&mut self, location: AssetLocation, value: T) -> Result<&T, RegistrationError>;
 
+    /// Registers `value`, or replaces the existing value in place if `location` is already
+    /// taken, keeping its index.
+    fn register_or_replace(
+        &mut self,
+        location: AssetLocation,
+        value: T,
+    ) -> Result<&T, RegistrationError>;
+
     fn freeze(&mut self);
+
+    fn tags(&self) -> &Tags;
+
+    /// Replaces the tag membership of this registry. Tags are data, so this is allowed even
+    /// once the registry is frozen.
+    fn bind_tags(&mut self, tags: Tags);
+
+    #[inline]
+    fn is_in_tag(&self, index: usize, tag: &TagKey<T>) -> bool {
+        self.tags().contains(tag, index)
+    }
 }
 
 /// # Registrable
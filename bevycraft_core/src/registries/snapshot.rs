@@ -0,0 +1,207 @@
+use {
+    crate::prelude::{AssetLocation, Registrable, Registry},
+    serde::{Deserialize, Serialize},
+    thiserror::Error,
+};
+
+/// An index → key table of a [`Registry`], meant to be persisted alongside data that stores
+/// registry indices so they can be remapped after the registry changes.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, Eq, PartialEq)]
+pub struct RegistrySnapshot {
+    keys: Vec<AssetLocation>,
+}
+
+impl RegistrySnapshot {
+    #[inline]
+    pub fn capture<T: Registrable>(registry: &impl Registry<T>) -> Self {
+        Self {
+            keys: registry.keys().cloned().collect(),
+        }
+    }
+
+    #[inline]
+    pub fn from_keys(keys: Vec<AssetLocation>) -> Self {
+        Self { keys }
+    }
+
+    #[inline(always)]
+    pub fn len(&self) -> usize {
+        self.keys.len()
+    }
+
+    #[inline(always)]
+    pub fn is_empty(&self) -> bool {
+        self.keys.is_empty()
+    }
+
+    #[inline]
+    pub fn key(&self, index: usize) -> Option<&AssetLocation> {
+        self.keys.get(index)
+    }
+
+    #[inline]
+    pub fn keys(&self) -> impl Iterator<Item = &AssetLocation> {
+        self.keys.iter()
+    }
+
+    /// Builds the table converting indices stored under this snapshot into indices of
+    /// `registry`, resolving keys that no longer exist according to `policy`.
+    pub fn remap<T: Registrable>(
+        &self,
+        registry: &impl Registry<T>,
+        policy: MissingKeyPolicy,
+    ) -> Result<IdRemap, RemapError> {
+        let mut table = Vec::with_capacity(self.keys.len());
+
+        for key in &self.keys {
+            let idx = match registry.key_to_idx(key) {
+                Some(idx) => idx,
+                None => policy.resolve(registry, key)?,
+            };
+
+            table.push(idx);
+        }
+
+        Ok(IdRemap::new(table))
+    }
+}
+
+/// What to do with stored keys that are absent from the current registry.
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq)]
+pub enum MissingKeyPolicy {
+    /// Replace them with the registry's default entry (e.g. `air` for blocks).
+    #[default]
+    ReplaceWithDefault,
+
+    /// Abort the remap.
+    Fail,
+}
+
+impl MissingKeyPolicy {
+    #[inline]
+    pub(crate) fn resolve<T: Registrable>(
+        self,
+        registry: &impl Registry<T>,
+        key: &AssetLocation,
+    ) -> Result<usize, RemapError> {
+        match self {
+            Self::ReplaceWithDefault => registry.default_idx().ok_or(RemapError::NoDefault),
+            Self::Fail => Err(RemapError::MissingKey(key.clone())),
+        }
+    }
+}
+
+/// A stored index → current index conversion table.
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+pub struct IdRemap {
+    table: Box<[usize]>,
+}
+
+impl IdRemap {
+    #[inline]
+    pub fn new(table: Vec<usize>) -> Self {
+        Self {
+            table: table.into_boxed_slice(),
+        }
+    }
+
+    #[inline(always)]
+    pub fn len(&self) -> usize {
+        self.table.len()
+    }
+
+    #[inline(always)]
+    pub fn is_empty(&self) -> bool {
+        self.table.is_empty()
+    }
+
+    #[inline(always)]
+    pub fn get(&self, stored: usize) -> Option<usize> {
+        self.table.get(stored).copied()
+    }
+
+    #[inline]
+    pub fn apply(&self, stored: usize) -> Result<usize, RemapError> {
+        self.get(stored).ok_or(RemapError::UnknownId(stored))
+    }
+
+    #[inline]
+    pub fn is_identity(&self) -> bool {
+        self.table.iter().enumerate().all(|(i, &idx)| i == idx)
+    }
+}
+
+#[derive(Error, Debug, Clone, Eq, PartialEq)]
+pub enum RemapError {
+    #[error("key `{0}` no longer exists in the registry")]
+    MissingKey(AssetLocation),
+
+    #[error("registry has no default entry to replace missing keys with")]
+    NoDefault,
+
+    #[error("stored id {0} is not covered by the snapshot")]
+    UnknownId(usize),
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, crate::prelude::*};
+
+    fn ordered(keys: &[&str]) -> OrderedRegistry<u32> {
+        let mut registry = OrderedRegistry::new();
+
+        for (value, key) in keys.iter().enumerate() {
+            registry
+                .register(AssetLocation::parse(key), value as u32)
+                .unwrap();
+        }
+
+        registry
+    }
+
+    #[test]
+    fn remaps_reordered_keys() {
+        let snapshot = RegistrySnapshot::capture(&ordered(&["a", "b", "c"]));
+        let remap = snapshot
+            .remap(&ordered(&["c", "a", "b"]), MissingKeyPolicy::Fail)
+            .unwrap();
+
+        assert_eq!(remap, IdRemap::new(vec![1, 2, 0]));
+        assert!(!remap.is_identity());
+        assert_eq!(remap.apply(3), Err(RemapError::UnknownId(3)));
+    }
+
+    #[test]
+    fn unchanged_registry_remaps_to_identity() {
+        let registry = ordered(&["a", "b"]);
+        let remap = RegistrySnapshot::capture(&registry)
+            .remap(&registry, MissingKeyPolicy::Fail)
+            .unwrap();
+
+        assert!(remap.is_identity());
+    }
+
+    #[test]
+    fn resolves_missing_keys_by_policy() {
+        let snapshot = RegistrySnapshot::from_keys(vec![
+            AssetLocation::parse("a"),
+            AssetLocation::parse("gone"),
+        ]);
+
+        let mut defaulted = DefaultedRegistry::new(AssetLocation::parse("air"), 0u32);
+        defaulted.register(AssetLocation::parse("a"), 1).unwrap();
+
+        assert_eq!(
+            snapshot.remap(&defaulted, MissingKeyPolicy::ReplaceWithDefault),
+            Ok(IdRemap::new(vec![1, 0]))
+        );
+        assert_eq!(
+            snapshot.remap(&ordered(&["a"]), MissingKeyPolicy::ReplaceWithDefault),
+            Err(RemapError::NoDefault)
+        );
+        assert_eq!(
+            snapshot.remap(&defaulted, MissingKeyPolicy::Fail),
+            Err(RemapError::MissingKey(AssetLocation::parse("gone")))
+        );
+    }
+}
@@ -0,0 +1,391 @@
+use {
+    crate::{
+        prelude::{AssetLocation, AssetLocationError, Registrable, Registry},
+        util::files::{collect_files, read_dir_sorted, relative_asset_path},
+    },
+    bevy::platform::collections::{HashMap, HashSet},
+    bitvec::vec::BitVec,
+    rapidhash::fast::RandomState,
+    serde::Deserialize,
+    std::{
+        fmt::{Debug, Display, Formatter},
+        fs,
+        hash::{Hash, Hasher},
+        marker::PhantomData,
+        path::{Path, PathBuf},
+    },
+    thiserror::Error,
+};
+
+const TAGS_DIRECTORY: &str = "tags";
+
+const TAG_EXTENSION: &str = "ron";
+
+pub(crate) const TAG_PREFIX: char = '#';
+
+/// A named group of entries of a registry of `T`, written as `#namespace:path`.
+pub struct TagKey<T> {
+    location: AssetLocation,
+    _marker: PhantomData<fn(T)>,
+}
+
+impl<T> TagKey<T> {
+    #[inline]
+    pub fn new(location: AssetLocation) -> Self {
+        Self {
+            location,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Parses a tag key, with or without the leading `#`.
+    #[inline]
+    pub fn parse(tag: &str) -> Self {
+        Self::try_parsing(tag).unwrap()
+    }
+
+    #[inline]
+    pub fn try_parsing(tag: &str) -> Result<Self, AssetLocationError> {
+        let tag = tag.strip_prefix(TAG_PREFIX).unwrap_or(tag);
+
+        AssetLocation::try_parsing(tag).map(Self::new)
+    }
+
+    #[inline(always)]
+    pub fn location(&self) -> &AssetLocation {
+        &self.location
+    }
+}
+
+impl<T> Clone for TagKey<T> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Self::new(self.location.clone())
+    }
+}
+
+impl<T> Eq for TagKey<T> {}
+
+impl<T> PartialEq for TagKey<T> {
+    #[inline(always)]
+    fn eq(&self, other: &Self) -> bool {
+        self.location == other.location
+    }
+}
+
+impl<T> Hash for TagKey<T> {
+    #[inline(always)]
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.location.hash(state);
+    }
+}
+
+impl<T> Debug for TagKey<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("TagKey").field(&self.location).finish()
+    }
+}
+
+impl<T> Display for TagKey<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}{}", TAG_PREFIX, self.location)
+    }
+}
+
+/// Resolved tag membership of a registry, stored as one bitset per tag indexed by registry id.
+#[derive(Debug, Clone, Default)]
+pub struct Tags {
+    sets: HashMap<AssetLocation, BitVec, RandomState>,
+}
+
+impl Tags {
+    #[inline]
+    pub fn contains<T>(&self, tag: &TagKey<T>, index: usize) -> bool {
+        self.sets
+            .get(&tag.location)
+            .and_then(|set| set.get(index).map(|bit| *bit))
+            .unwrap_or(false)
+    }
+
+    #[inline]
+    pub fn has_tag<T>(&self, tag: &TagKey<T>) -> bool {
+        self.sets.contains_key(&tag.location)
+    }
+
+    #[inline]
+    pub fn members<T>(&self, tag: &TagKey<T>) -> impl Iterator<Item = usize> + '_ {
+        self.sets
+            .get(&tag.location)
+            .into_iter()
+            .flat_map(|set| set.iter_ones())
+    }
+
+    #[inline]
+    pub fn keys(&self) -> impl Iterator<Item = &AssetLocation> {
+        self.sets.keys()
+    }
+
+    #[inline]
+    pub fn insert(&mut self, tag: AssetLocation, members: impl IntoIterator<Item = usize>) {
+        let set = self.sets.entry(tag).or_default();
+
+        for idx in members {
+            if idx >= set.len() {
+                set.resize(idx + 1, false);
+            }
+
+            set.set(idx, true);
+        }
+    }
+
+    #[inline(always)]
+    pub fn len(&self) -> usize {
+        self.sets.len()
+    }
+
+    #[inline(always)]
+    pub fn is_empty(&self) -> bool {
+        self.sets.is_empty()
+    }
+}
+
+/// Contents of a tag file. Entries prefixed with `#` include another tag.
+#[derive(Deserialize, Debug, Clone)]
+#[serde(deny_unknown_fields)]
+struct TagFile {
+    values: Vec<Box<str>>,
+}
+
+#[derive(Debug, Clone)]
+enum TagEntry {
+    Element(AssetLocation),
+    Tag(AssetLocation),
+}
+
+/// Loads every `<namespace>/tags/<directory>/**/*.ron` file under `assets` and resolves it
+/// against `registry`, flattening nested tags.
+///
+/// A tag takes its namespace from the directory its file sits in, so every tag is defined by
+/// exactly one file.
+pub fn load_tags<T: Registrable>(
+    registry: &impl Registry<T>,
+    assets: &Path,
+    directory: &str,
+) -> Result<Tags, TagError> {
+    let mut raw: HashMap<AssetLocation, Vec<TagEntry>, RandomState> =
+        HashMap::with_hasher(RandomState::new());
+
+    for namespace in read_dir_sorted(assets)? {
+        let root = namespace.join(TAGS_DIRECTORY).join(directory);
+
+        let Some(namespace) = namespace.file_name().and_then(|n| n.to_str()) else {
+            continue;
+        };
+
+        let mut files = Vec::new();
+        collect_files(&root, TAG_EXTENSION, &mut files)?;
+
+        for file in files {
+            let relative = relative_asset_path(&root, &file).unwrap();
+            let tag = AssetLocation::try_with_custom_namespace(namespace, &relative)?;
+
+            let bytes = fs::read(&file)?;
+            let parsed = ron::de::from_bytes::<TagFile>(&bytes)
+                .map_err(|e| TagError::Deserialize(file.clone(), Box::new(e)))?;
+
+            let mut entries = Vec::with_capacity(parsed.values.len());
+
+            for value in parsed.values.iter() {
+                entries.push(match value.strip_prefix(TAG_PREFIX) {
+                    Some(nested) => TagEntry::Tag(AssetLocation::try_parsing(nested)?),
+                    None => TagEntry::Element(AssetLocation::try_parsing(value)?),
+                });
+            }
+
+            raw.insert(tag, entries);
+        }
+    }
+
+    let mut tags = Tags::default();
+    let mut resolving = HashSet::default();
+
+    let mut keys: Vec<AssetLocation> = raw.keys().cloned().collect();
+    keys.sort();
+
+    for tag in keys {
+        resolve_tag(registry, &raw, &tag, &mut tags, &mut resolving)?;
+    }
+
+    Ok(tags)
+}
+
+fn resolve_tag<T: Registrable>(
+    registry: &impl Registry<T>,
+    raw: &HashMap<AssetLocation, Vec<TagEntry>, RandomState>,
+    tag: &AssetLocation,
+    tags: &mut Tags,
+    resolving: &mut HashSet<AssetLocation>,
+) -> Result<(), TagError> {
+    if tags.sets.contains_key(tag) {
+        return Ok(());
+    }
+
+    if !resolving.insert(tag.clone()) {
+        return Err(TagError::Cycle(tag.clone()));
+    }
+
+    let mut members = BitVec::repeat(false, registry.len());
+
+    for entry in &raw[tag] {
+        match entry {
+            TagEntry::Element(element) => {
+                let Some(idx) = registry.key_to_idx(element) else {
+                    return Err(TagError::UnknownEntry {
+                        tag: tag.clone(),
+                        entry: element.clone(),
+                    });
+                };
+
+                members.set(idx, true);
+            }
+            TagEntry::Tag(nested) => {
+                if !raw.contains_key(nested) {
+                    return Err(TagError::UnknownTag {
+                        tag: tag.clone(),
+                        nested: nested.clone(),
+                    });
+                }
+
+                resolve_tag(registry, raw, nested, tags, resolving)?;
+
+                *members.as_mut_bitslice() |= tags.sets[nested].as_bitslice();
+            }
+        }
+    }
+
+    resolving.remove(tag);
+    tags.sets.insert(tag.clone(), members);
+
+    Ok(())
+}
+
+#[derive(Error, Debug)]
+pub enum TagError {
+    #[error("io error: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("failed to deserialize `{path}`: {error}", path = .0.display(), error = .1)]
+    Deserialize(PathBuf, Box<ron::de::SpannedError>),
+
+    #[error("invalid location: {0}")]
+    InvalidLocation(#[from] AssetLocationError),
+
+    #[error("tag `#{tag}` references unknown entry `{entry}`")]
+    UnknownEntry {
+        tag: AssetLocation,
+        entry: AssetLocation,
+    },
+
+    #[error("tag `#{tag}` includes unknown tag `#{nested}`")]
+    UnknownTag {
+        tag: AssetLocation,
+        nested: AssetLocation,
+    },
+
+    #[error("tag `#{0}` includes itself")]
+    Cycle(AssetLocation),
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, crate::prelude::OrderedRegistry};
+
+    const BLOCKS: &str = "blocks";
+
+    /// Writes each `(path, values)` as a block tag of the default namespace under a fresh assets
+    /// directory named after `test`.
+    fn write_tags(test: &str, tags: &[(&str, &str)]) -> PathBuf {
+        let assets =
+            std::env::temp_dir().join(format!("bevycraft_tags_{test}_{}", std::process::id()));
+
+        let _ = fs::remove_dir_all(&assets);
+
+        for (path, values) in tags {
+            let file = assets
+                .join(AssetLocation::DEFAULT_NAMESPACE)
+                .join(TAGS_DIRECTORY)
+                .join(BLOCKS)
+                .join(format!("{path}.{TAG_EXTENSION}"));
+
+            fs::create_dir_all(file.parent().unwrap()).unwrap();
+            fs::write(file, format!("(values: [{values}])")).unwrap();
+        }
+
+        assets
+    }
+
+    fn registry() -> OrderedRegistry<u32> {
+        let mut registry = OrderedRegistry::new();
+
+        for (value, key) in ["oak_log", "birch_log", "oak_planks", "dirt"]
+            .into_iter()
+            .enumerate()
+        {
+            registry
+                .register(AssetLocation::parse(key), value as u32)
+                .unwrap();
+        }
+
+        registry
+    }
+
+    #[test]
+    fn flattens_nested_tags() {
+        let assets = write_tags(
+            "nested",
+            &[
+                ("logs", r#""oak_log", "birch_log""#),
+                ("mineable/axe", r##""#bevycraft:logs", "oak_planks""##),
+            ],
+        );
+
+        let tags = load_tags(&registry(), &assets, BLOCKS).unwrap();
+
+        let logs = TagKey::<u32>::parse("#logs");
+        let axe = TagKey::<u32>::parse("#bevycraft:mineable/axe");
+
+        assert_eq!(tags.len(), 2);
+        assert_eq!(tags.members(&logs).collect::<Vec<_>>(), [0, 1]);
+        assert_eq!(tags.members(&axe).collect::<Vec<_>>(), [0, 1, 2]);
+        assert!(!tags.contains(&logs, 2));
+        assert!(!tags.contains(&axe, 3));
+        assert!(!tags.has_tag(&TagKey::<u32>::parse("dirt")));
+    }
+
+    #[test]
+    fn rejects_cycles() {
+        let assets = write_tags("cycle", &[("a", r##""#b""##), ("b", r##""#a""##)]);
+
+        assert!(matches!(
+            load_tags(&registry(), &assets, BLOCKS),
+            Err(TagError::Cycle(_))
+        ));
+    }
+
+    #[test]
+    fn rejects_unknown_references() {
+        let assets = write_tags("unknown_entry", &[("logs", r#""oak_log", "spruce_log""#)]);
+
+        assert!(matches!(
+            load_tags(&registry(), &assets, BLOCKS),
+            Err(TagError::UnknownEntry { entry, .. }) if entry == AssetLocation::parse("spruce_log")
+        ));
+
+        let assets = write_tags("unknown_tag", &[("logs", r##""#wood""##)]);
+
+        assert!(matches!(
+            load_tags(&registry(), &assets, BLOCKS),
+            Err(TagError::UnknownTag { nested, .. }) if nested == AssetLocation::parse("wood")
+        ));
+    }
+}
@@ -0,0 +1,43 @@
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+/// Recursively collects every file with the given extension under `directory`, sorted by path.
+pub(crate) fn collect_files(
+    directory: &Path,
+    extension: &str,
+    files: &mut Vec<PathBuf>,
+) -> io::Result<()> {
+    for path in read_dir_sorted(directory)? {
+        if path.is_dir() {
+            collect_files(&path, extension, files)?;
+        } else if path.extension().is_some_and(|e| e == extension) {
+            files.push(path);
+        }
+    }
+
+    Ok(())
+}
+
+/// Lists the entries of `directory` sorted by path, or nothing if it does not exist.
+pub(crate) fn read_dir_sorted(directory: &Path) -> io::Result<Vec<PathBuf>> {
+    if !directory.is_dir() {
+        return Ok(Vec::new());
+    }
+
+    let mut paths = fs::read_dir(directory)?
+        .map(|entry| entry.map(|e| e.path()))
+        .collect::<io::Result<Vec<_>>>()?;
+
+    paths.sort();
+
+    Ok(paths)
+}
+
+/// Turns `file` into an asset path relative to `root`, without extension and with `/` separators.
+pub(crate) fn relative_asset_path(root: &Path, file: &Path) -> Option<String> {
+    let relative = file.strip_prefix(root).ok()?.with_extension("");
+
+    Some(relative.to_string_lossy().replace('\\', "/"))
+}
@@ -1,7 +1,7 @@
 use {
     proc_macro::TokenStream,
     proc_macro_crate::{crate_name, FoundCrate},
-    quote::{format_ident, quote},
+    quote::{format_ident, quote, ToTokens},
     syn::{
         parse::Parse, parse_macro_input, DeriveInput, Expr, ExprLit, Ident, Lit, MetaNameValue, Token,
         Type, Visibility,
@@ -17,12 +17,29 @@ struct Entry {
     def: Expr,
 }
 
-struct RegisterInput(Vec<Entry>);
+struct RegisterFn {
+    vis: Visibility,
+    name: Ident,
+}
+
+struct RegisterInput(Option<RegisterFn>, Vec<Entry>);
 
 impl Parse for RegisterInput {
     fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let mut register_fn = None;
         let mut entries = Vec::new();
 
+        let fork = input.fork();
+
+        if fork.parse::<Visibility>().is_ok() && fork.peek(Token![fn]) {
+            let vis: Visibility = input.parse()?;
+            input.parse::<Token![fn]>()?;
+            let name: Ident = input.parse()?;
+            input.parse::<Token![;]>()?;
+
+            register_fn = Some(RegisterFn { vis, name });
+        }
+
         while !input.is_empty() {
             let vis: Visibility = input.parse()?;
 
@@ -71,13 +88,15 @@ impl Parse for RegisterInput {
             });
         }
 
-        Ok(Self(entries))
+        Ok(Self(register_fn, entries))
     }
 }
 
+/// Declares `Holder` statics along with a function registering them in declaration order,
+/// named by an optional leading `fn name;` (`register` by default).
 #[proc_macro]
 pub fn context(input: TokenStream) -> TokenStream {
-    let RegisterInput(entries) = parse_macro_input!(input as RegisterInput);
+    let RegisterInput(register_fn, entries) = parse_macro_input!(input as RegisterInput);
     let bevycraft_core = bevycraft_core();
 
     let decls: Vec<proc_macro2::TokenStream> = entries
@@ -90,43 +109,39 @@ pub fn context(input: TokenStream) -> TokenStream {
         })
         .collect();
 
-    let mut groups: Vec<(String, Vec<&Entry>)> = Vec::new();
-    for entry in &entries {
-        let key = quote!(#(entry.key)).to_string();
+    let Some(first) = entries.first() else {
+        return quote! {}.into();
+    };
 
-        match groups.iter_mut().find(|(k, _)| k == &key) {
-            Some(g) => g.1.push(entry),
-            None => groups.push((key, vec![entry])),
-        }
-    }
+    let ty = &first.ty;
+    let ty_name = ty.to_token_stream().to_string();
 
-    let reg_blocks: Vec<proc_macro2::TokenStream> = groups
+    if let Some(other) = entries
         .iter()
-        .map(|(_, group)| {
-            let ty = &group[0].ty;
-            let ops = group.iter().map(|e| {
-                let name = &e.name;
-                quote! { #name.registrar(registry); }
-            });
-            quote! {
-                {
-                    let registry = &mut *#bevycraft_core::prelude::Registrar::<#ty>::write_to_registry();
+        .find(|e| e.ty.to_token_stream().to_string() != ty_name)
+    {
+        return syn::Error::new_spanned(&other.ty, "all entries of a context must share one type")
+            .to_compile_error()
+            .into();
+    }
 
-                    #(#ops)*
-                }
-            }
-        })
-        .collect();
+    let (fn_vis, fn_name) = match register_fn {
+        Some(RegisterFn { vis, name }) => (quote! { #vis }, name),
+        None => (quote! { pub }, format_ident!("register")),
+    };
+
+    let names = entries.iter().map(|e| &e.name);
 
     quote! {
         #(#decls)*
 
-        const _: () = {
-            #[::ctor::ctor(unsafe)]
-            fn __register() {
-                #(#reg_blocks)*
-            }
-        };
+        #fn_vis fn #fn_name(
+            registry: &mut impl #bevycraft_core::prelude::Registry<#ty>,
+        ) -> ::std::result::Result<(), #bevycraft_core::prelude::RegistrationError> {
+            #(#names.registrar(registry)?;)*
+
+            Ok(())
+        }
     }
     .into()
 }
@@ -174,23 +189,54 @@ pub fn derive_registrar(input: TokenStream) -> TokenStream {
             static __REGISTRY: ::std::sync::LazyLock<
                 ::parking_lot::RwLock<#registry_type>
             > = ::std::sync::LazyLock::new(|| {
-                ::parking_lot::RwLock::new(#registry_init)
+                ::parking_lot::RwLock::new(
+                    <#bevycraft_core::prelude::Registrar<#name> as #bevycraft_core::prelude::RegistrarOps<#name>>::isolated()
+                )
             });
 
             static __LOCK: ::std::sync::atomic::AtomicBool = ::std::sync::atomic::AtomicBool::new(false);
 
             impl #bevycraft_core::prelude::RegistrarOps<#name> for #bevycraft_core::prelude::Registrar<#name> #extra_where {
+                type Registry = #registry_type;
+
                 #[inline]
                 fn read_from_registry<'a>() -> ::parking_lot::RwLockReadGuard<'a, #registry_type> {
                     __REGISTRY.read()
                 }
 
-                fn write_to_registry<'a>() -> ::parking_lot::RwLockWriteGuard<'a, #registry_type> {
+                #[inline]
+                fn isolated() -> #registry_type {
+                    #registry_init
+                }
+
+                fn write_to_registry<'a>() -> ::std::result::Result<
+                    ::parking_lot::RwLockWriteGuard<'a, #registry_type>,
+                    #bevycraft_core::prelude::RegistrationError,
+                > {
                     if __LOCK.load(::std::sync::atomic::Ordering::Acquire) {
-                        panic!("Tried writing to {}'s registry, but it was already locked", stringify!(#name));
+                        return Err(#bevycraft_core::prelude::RegistrationError::FrozenRegistry);
                     }
 
-                    __REGISTRY.write()
+                    let registry = __REGISTRY.write();
+
+                    // The registry may have been locked while waiting for the guard.
+                    if __LOCK.load(::std::sync::atomic::Ordering::Acquire) {
+                        return Err(#bevycraft_core::prelude::RegistrationError::FrozenRegistry);
+                    }
+
+                    Ok(registry)
+                }
+
+                fn lock_registry() {
+                    let mut registry = __REGISTRY.write();
+
+                    #bevycraft_core::prelude::Registry::freeze(&mut *registry);
+                    __LOCK.store(true, ::std::sync::atomic::Ordering::Release);
+                }
+
+                #[inline]
+                fn is_locked() -> bool {
+                    __LOCK.load(::std::sync::atomic::Ordering::Acquire)
                 }
             }
         };
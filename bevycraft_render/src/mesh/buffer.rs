@@ -17,17 +17,19 @@ pub struct VertexBufferSet {
 }
 
 impl VertexBufferSet {
+    /// Pushes `quads`, scaling their vertex colors by `shade`.
     #[inline]
     pub fn push_quads_with_offset<'a>(
         &mut self,
         quads: impl Iterator<Item = &'a Quad>,
         offset: impl Into<[f32; 3]>,
         tint: Option<[f32; 3]>,
+        shade: f32,
     ) {
         let offset = offset.into();
 
         for quad in quads {
-            self.push_quad_with_offset(quad, offset, tint)
+            self.push_quad_with_offset(quad, offset, tint, shade)
         }
     }
 
@@ -37,12 +39,15 @@ impl VertexBufferSet {
         quad: &Quad,
         offset: impl Into<[f32; 3]>,
         tint: Option<[f32; 3]>,
+        shade: f32,
     ) {
-        match quad.render_mode {
-            RenderMode::Opaque => self.opaque.push_quad_with_offset(quad, offset, tint),
-            RenderMode::Cutout => self.cutout.push_quad_with_offset(quad, offset, tint),
-            RenderMode::Translucent => self.translucent.push_quad_with_offset(quad, offset, tint),
-        }
+        let buffer = match quad.render_mode {
+            RenderMode::Opaque => &mut self.opaque,
+            RenderMode::Cutout => &mut self.cutout,
+            RenderMode::Translucent => &mut self.translucent,
+        };
+
+        buffer.push_shaded_quad_with_offset(quad, offset, tint, shade)
     }
 }
 
@@ -99,6 +104,18 @@ impl VertexBuffer {
         quad: &Quad,
         offset: impl Into<[f32; 3]>,
         tint: Option<[f32; 3]>,
+    ) {
+        self.push_shaded_quad_with_offset(quad, offset, tint, 1.0)
+    }
+
+    /// Pushes `quad` with its vertex colors scaled by `shade`, e.g. the light of its face.
+    #[inline]
+    pub fn push_shaded_quad_with_offset(
+        &mut self,
+        quad: &Quad,
+        offset: impl Into<[f32; 3]>,
+        tint: Option<[f32; 3]>,
+        shade: f32,
     ) {
         let offset = offset.into();
         let positions = quad.positions.map(|mut pos| {
@@ -131,7 +148,9 @@ impl VertexBuffer {
             NEUTRAL_TINT
         };
 
-        self.colors.extend_from_slice(&tint);
+        self.colors.extend_from_slice(&tint.map(|[r, g, b, a]| {
+            [r * shade, g * shade, b * shade, a]
+        }));
 
         let i = self.next;
 
@@ -1,13 +1,19 @@
 use {
     crate::{
         mesh::{buffer::VertexBufferSet, input::MeshInput},
-        prelude::{Direction, OcclusionMask},
+        prelude::{BlockModel, Direction, OcclusionMask},
     },
     bevy::prelude::{IVec3, Mesh},
-    bevycraft_core::blocks::{AIR, WATER},
-    bevycraft_world::prelude::CHUNK_SIZE,
+    bevycraft_core::{
+        blocks::AIR,
+        prelude::{fluid_height, MAX_LIGHT},
+    },
+    bevycraft_world::prelude::{LightLevel, CHUNK_SIZE},
 };
 
+/// Brightness of faces in complete darkness, so caves are not pitch black.
+const MIN_BRIGHTNESS: f32 = 0.05;
+
 pub struct ChunkMeshOutput {
     pub opaque: Option<Mesh>,
     pub cutout: Option<Mesh>,
@@ -49,41 +55,35 @@ fn mesh(input: MeshInput) -> ChunkMeshOutput {
             for z in 0..CHUNK_SIZE {
                 let local = IVec3::new(x, y, z);
 
-                let id = input.get_id_at(local);
+                let block = input.get_block_at(local);
 
-                if id == *AIR {
+                if block == *AIR {
                     continue;
                 };
 
-                let Some(model) = input.get_model_of(id) else {
+                let Some(model) = input.get_model_of(block) else {
                     continue;
                 };
 
                 let offset = [x as f32, y as f32, z as f32];
 
+                if input.is_fluid(block) {
+                    mesh_fluid(&input, &mut bufs, local, block, model);
+
+                    continue;
+                }
+
                 bufs.push_quads_with_offset(
                     model.iter_inner_quads(),
                     offset,
                     Some([0.2, 0.8, 0.2]),
+                    brightness(input.get_light_at(local)),
                 );
 
                 for dir in Direction::ALL {
                     let nb_pos = local + dir.offset();
                     let nb_mask = sample_neighbor_mask(&input, nb_pos, dir);
-
-                    if dir == Direction::PosY && id != *WATER {
-                        let nb_id = input.get_id_at(nb_pos);
-
-                        if nb_id == *WATER {
-                            bufs.push_quads_with_offset(
-                                model.iter_outer_quads_at(dir),
-                                offset,
-                                Some([0.2, 0.8, 0.2]),
-                            );
-
-                            continue;
-                        }
-                    }
+                    let shade = brightness(input.get_light_at(nb_pos));
 
                     if model.mask(dir).is_occluded_by(nb_mask) {
                         continue;
@@ -95,6 +95,7 @@ fn mesh(input: MeshInput) -> ChunkMeshOutput {
                             .filter(|&q| !q.mask.is_occluded_by(nb_mask)),
                         offset,
                         Some([0.2, 0.8, 0.2]),
+                        shade,
                     );
                 }
             }
@@ -104,13 +105,143 @@ fn mesh(input: MeshInput) -> ChunkMeshOutput {
     ChunkMeshOutput::try_from_set(bufs)
 }
 
+/// Meshes a single block model on its own, with every face visible and fully lit.
+pub fn mesh_block_model(model: &BlockModel) -> ChunkMeshOutput {
+    let mut bufs = VertexBufferSet::default();
+
+    bufs.push_quads_with_offset(
+        model.iter_inner_quads(),
+        [0.0; 3],
+        Some([0.2, 0.8, 0.2]),
+        1.0,
+    );
+
+    for dir in Direction::ALL {
+        bufs.push_quads_with_offset(
+            model.iter_outer_quads_at(dir),
+            [0.0; 3],
+            Some([0.2, 0.8, 0.2]),
+            1.0,
+        );
+    }
+
+    ChunkMeshOutput::try_from_set(bufs)
+}
+
+/// Meshes a fluid block, raising the top of its faces to the surface height at each corner.
+fn mesh_fluid(
+    input: &MeshInput,
+    bufs: &mut VertexBufferSet,
+    local: IVec3,
+    block: usize,
+    model: &BlockModel,
+) {
+    let heights = if input.get_block_at(local + Direction::PosY.offset()) == block {
+        [[1.0; 2]; 2]
+    } else {
+        [0, 1].map(|cx| [0, 1].map(|cz| corner_height(input, local, block, cx, cz)))
+    };
+
+    let offset = local.as_vec3().to_array();
+
+    for dir in Direction::ALL {
+        let nb_pos = local + dir.offset();
+
+        if input.get_block_at(nb_pos) == block {
+            continue;
+        }
+
+        if dir != Direction::PosY
+            && model
+                .mask(dir)
+                .is_occluded_by(sample_neighbor_mask(input, nb_pos, dir))
+        {
+            continue;
+        }
+
+        let shade = brightness(input.get_light_at(nb_pos));
+
+        for quad in model.iter_outer_quads_at(dir) {
+            let mut quad = *quad;
+
+            for [x, y, z] in quad.positions.iter_mut() {
+                if *y > 0.0 {
+                    *y = surface_height(heights, *x, *z);
+                }
+            }
+
+            bufs.push_quad_with_offset(&quad, offset, None, shade);
+        }
+    }
+}
+
+/// Surface height at corner `(cx, cz)` of the fluid at `local`, averaged over the 4 blocks
+/// sharing that corner. Fluid below fluid reaches the top, solid blocks and blocks outside the
+/// loaded neighbors are left out and other blocks pull the surface down.
+fn corner_height(input: &MeshInput, local: IVec3, block: usize, cx: i32, cz: i32) -> f32 {
+    let states = input.states();
+
+    let mut total = 0.0;
+    let mut count = 0;
+
+    for dx in [cx - 1, cx] {
+        for dz in [cz - 1, cz] {
+            let cell = local + IVec3::new(dx, 0, dz);
+
+            let Some(state) = input.try_get_state_at(cell) else {
+                continue;
+            };
+
+            if states.block_of(state) == block {
+                if input.get_block_at(cell + Direction::PosY.offset()) == block {
+                    return 1.0;
+                }
+
+                total += fluid_height(state).unwrap_or(1.0);
+                count += 1;
+            } else if !states.covers_face(state, Direction::PosY) {
+                count += 1;
+            }
+        }
+    }
+
+    if count == 0 {
+        return fluid_height(input.get_state_at(local)).unwrap_or(1.0);
+    }
+
+    total / count as f32
+}
+
+/// Bilinear interpolation of the corner `heights`, indexed `[x][z]`, at `(x, z)`.
+#[inline(always)]
+fn surface_height(heights: [[f32; 2]; 2], x: f32, z: f32) -> f32 {
+    let near = heights[0][0] + (heights[1][0] - heights[0][0]) * x;
+    let far = heights[0][1] + (heights[1][1] - heights[0][1]) * x;
+
+    near + (far - near) * z
+}
+
 #[inline(always)]
 fn sample_neighbor_mask(input: &MeshInput, nb: IVec3, dir: Direction) -> OcclusionMask {
+    if input.is_fluid(input.get_block_at(nb)) {
+        return OcclusionMask::EMPTY;
+    }
+
     let model = input.get_model_at(nb);
 
     model.map(|m| m.mask(!dir)).unwrap_or(OcclusionMask::EMPTY)
 }
 
+/// Vertex brightness of a light level, following the usual inverse curve so mid levels stay
+/// readable.
+#[inline(always)]
+fn brightness(light: LightLevel) -> f32 {
+    let level = light.max() as f32 / MAX_LIGHT as f32;
+    let curve = level / (4.0 - 3.0 * level);
+
+    MIN_BRIGHTNESS + (1.0 - MIN_BRIGHTNESS) * curve
+}
+
 #[inline(always)]
 pub fn border_coords(nb: IVec3, s: i32) -> (i32, i32) {
     let x = nb.x.rem_euclid(s);
@@ -1,8 +1,10 @@
 use {
     crate::prelude::{BlockModel, Direction, ModelCache},
     bevy::prelude::IVec3,
-    bevycraft_core::prelude::Block,
-    bevycraft_world::prelude::{ChunkMap, ChunkPos, ChunkStorage, CHUNK_SIZE},
+    bevycraft_core::prelude::{Block, BlockFlags, BlockStates},
+    bevycraft_world::prelude::{
+        ChunkLight, ChunkMap, ChunkPos, ChunkStorage, LightLevel, CHUNK_SIZE,
+    },
     std::sync::Arc,
 };
 
@@ -11,7 +13,10 @@ pub struct MeshInput {
     _pos: ChunkPos,
     storage: Arc<ChunkStorage>,
     neighbors: [Option<Arc<ChunkStorage>>; 6],
+    light: Arc<ChunkLight>,
+    neighbor_lights: [Option<Arc<ChunkLight>>; 6],
     model_cache: ModelCache<Block, BlockModel>,
+    states: &'static BlockStates,
 }
 
 impl MeshInput {
@@ -27,38 +32,88 @@ impl MeshInput {
             chunk_map.get(&nb_pos).map(|nb| nb.storage.clone())
         });
 
+        let neighbor_lights = Direction::ALL.map(|dir| {
+            let nb_pos = ChunkPos::from(pos + dir.offset());
+
+            chunk_map.get(&nb_pos).map(|nb| nb.light.clone())
+        });
+
+        let light = chunk_map
+            .get(&pos)
+            .map(|chunk| chunk.light.clone())
+            .unwrap_or_default();
+
         Self {
             _pos: pos,
             storage,
             neighbors,
+            light,
+            neighbor_lights,
             model_cache,
+            states: BlockStates::global(),
         }
     }
 
     #[inline]
     pub fn get_model_at(&self, pos: IVec3) -> Option<&BlockModel> {
-        let id = self.get_id_at(pos);
+        let block = self.get_block_at(pos);
 
-        self.get_model_of(id)
+        self.get_model_of(block)
     }
 
     #[inline]
-    pub fn get_model_of(&self, id: usize) -> Option<&BlockModel> {
-        self.model_cache.get(id)
+    pub fn get_model_of(&self, block: usize) -> Option<&BlockModel> {
+        self.model_cache.get(block)
     }
 
     #[inline]
-    pub fn get_id_at(&self, pos: IVec3) -> usize {
+    pub fn get_block_at(&self, pos: IVec3) -> usize {
+        self.states.block_of(self.get_state_at(pos))
+    }
+
+    #[inline]
+    pub fn get_state_at(&self, pos: IVec3) -> usize {
+        self.try_get_state_at(pos).unwrap_or(0)
+    }
+
+    /// State at `pos`, or `None` where it is not known: in a neighbor that is not loaded, or
+    /// past an edge or corner of the chunk.
+    #[inline]
+    pub fn try_get_state_at(&self, pos: IVec3) -> Option<usize> {
         if in_bounds(pos) {
-            return self.storage.get(pos);
+            return Some(self.storage.get(pos));
         }
 
-        let dir = get_direction(pos).unwrap();
-
-        self.neighbors[dir as usize]
+        self.neighbors[get_direction(pos)? as usize]
             .as_ref()
             .map(|nb| nb.get(pos.rem_euclid(IVec3::splat(CHUNK_SIZE))))
-            .unwrap_or(0)
+    }
+
+    #[inline]
+    pub fn is_fluid(&self, block: usize) -> bool {
+        self.states.flags_of(block).contains(BlockFlags::FLUID)
+    }
+
+    #[inline(always)]
+    pub fn states(&self) -> &'static BlockStates {
+        self.states
+    }
+
+    /// Light at `pos`, or full sky light where no neighbor is loaded.
+    #[inline]
+    pub fn get_light_at(&self, pos: IVec3) -> LightLevel {
+        if in_bounds(pos) {
+            return self.light.sample(pos);
+        }
+
+        let Some(dir) = get_direction(pos) else {
+            return LightLevel::FULL_SKY;
+        };
+
+        self.neighbor_lights[dir as usize]
+            .as_ref()
+            .map(|nb| nb.sample(pos.rem_euclid(IVec3::splat(CHUNK_SIZE))))
+            .unwrap_or(LightLevel::FULL_SKY)
     }
 
     #[inline]
@@ -1,10 +1,11 @@
 use crate::mesh::mask::OcclusionMask;
 use crate::prelude::TextureId;
 use bevy::math::EulerRot;
-use bevy::prelude::{IVec3, Quat, Vec3};
+use bevy::prelude::{Quat, Vec3};
 use serde::{Deserialize, Serialize};
 use std::fmt::{Display, Formatter};
-use std::ops::Not;
+
+pub use bevycraft_core::prelude::Direction;
 
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub struct Quad {
@@ -196,103 +197,6 @@ impl Quad {
     }
 }
 
-#[derive(Deserialize, Debug, Copy, Clone, Eq, PartialEq, Hash)]
-pub enum Direction {
-    #[serde(rename = "east")]
-    PosX = 0,
-    #[serde(rename = "west")]
-    NegX = 1,
-
-    #[serde(rename = "up")]
-    PosY = 2,
-    #[serde(rename = "down")]
-    NegY = 3,
-
-    #[serde(rename = "south")]
-    PosZ = 4,
-    #[serde(rename = "north")]
-    NegZ = 5,
-}
-
-impl From<usize> for Direction {
-    #[inline(always)]
-    fn from(value: usize) -> Self {
-        match value {
-            0 => Direction::PosX,
-            1 => Direction::NegX,
-            2 => Direction::PosY,
-            3 => Direction::NegY,
-            4 => Direction::PosZ,
-            5 => Direction::NegZ,
-            _ => panic!("invalid direction value: {}", value),
-        }
-    }
-}
-
-impl Direction {
-    pub const ALL: [Self; 6] = [
-        Self::PosX,
-        Self::NegX,
-        Self::PosY,
-        Self::NegY,
-        Self::PosZ,
-        Self::NegZ,
-    ];
-    
-    #[inline(always)]
-    pub const fn offset(self) -> IVec3 {
-        match self {
-            Direction::PosX => IVec3::new(1, 0, 0),
-            Direction::NegX => IVec3::new(-1, 0, 0),
-            Direction::PosY => IVec3::new(0, 1, 0),
-            Direction::NegY => IVec3::new(0, -1, 0),
-            Direction::PosZ => IVec3::new(0, 0, 1),
-            Direction::NegZ => IVec3::new(0, 0, -1),
-        }
-    }
-
-    #[inline(always)]
-    pub const fn get_normal(self) -> [f32; 3] {
-        match self {
-            Direction::PosX => [1.0, 0.0, 0.0],
-            Direction::NegX => [-1.0, 0.0, 0.0],
-            Direction::PosY => [0.0, 1.0, 0.0],
-            Direction::NegY => [0.0, -1.0, 0.0],
-            Direction::PosZ => [0.0, 0.0, 1.0],
-            Direction::NegZ => [0.0, 0.0, -1.0],
-        }
-    }
-}
-
-impl Display for Direction {
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        match self {
-            Direction::PosX => f.write_str("east"),
-            Direction::NegX => f.write_str("west"),
-            Direction::PosY => f.write_str("up"),
-            Direction::NegY => f.write_str("down"),
-            Direction::PosZ => f.write_str("south"),
-            Direction::NegZ => f.write_str("north"),
-        }
-    }
-}
-
-impl Not for Direction {
-    type Output = Self;
-
-    #[inline(always)]
-    fn not(self) -> Self::Output {
-        match self {
-            Direction::PosX => Direction::NegX,
-            Direction::NegX => Direction::PosX,
-            Direction::PosY => Direction::NegY,
-            Direction::NegY => Direction::PosY,
-            Direction::PosZ => Direction::NegZ,
-            Direction::NegZ => Direction::PosZ,
-        }
-    }
-}
-
 #[derive(Deserialize, Serialize, Debug, Clone, Copy, Eq, PartialEq, Hash)]
 #[serde(rename_all = "lowercase")]
 pub enum RenderMode {
@@ -3,8 +3,8 @@ use {
     crate::{
         prelude::{ChunkEntityMap, MeshingQueue},
         renderer::system::{
-            cleanup_chunk_entities, dispatch_mesh_tasks, poll_mesh_tasks, remesh_dirty_chunks,
-            trigger_chunk_meshing,
+            cleanup_chunk_entities, dispatch_mesh_tasks, mesh_falling_blocks, poll_mesh_tasks,
+            remesh_dirty_chunks, trigger_chunk_meshing,
         },
     },
     bevy::{
@@ -38,6 +38,7 @@ impl<S: States> Plugin for ChunkRenderPlugin<S> {
                         .in_set(ChunkSet::Cleanup)
                         .run_if(any_chunk_dirty),
                     cleanup_chunk_entities.in_set(ChunkSet::Cleanup),
+                    mesh_falling_blocks.in_set(ChunkSet::Cleanup),
                 )
                     .run_if(in_state(self.run_in_state.clone())),
             );
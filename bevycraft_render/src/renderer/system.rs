@@ -1,8 +1,8 @@
 use {
     crate::{
         prelude::{
-            mesh_chunk, ArrayTexture, BlockModel, ChunkEntityMap, ChunkMeshLayer, ChunkMeshRoot,
-            Direction, MeshInput, ModelCache, RenderMode,
+            mesh_block_model, mesh_chunk, ArrayTexture, BlockModel, ChunkEntityMap, ChunkMeshLayer,
+            ChunkMeshRoot, Direction, MeshInput, ModelCache, RenderMode,
         },
         renderer::component::{BatchOutput, InflightBatch, MeshingQueue},
     },
@@ -10,13 +10,15 @@ use {
         camera::primitives::{Aabb, Frustum},
         platform::collections::HashSet,
         prelude::{
-            Assets, Children, Commands, Mesh, Mesh3d, MeshMaterial3d, MessageReader, Query, Res,
-            ResMut, Transform, Vec3, Visibility,
+            Added, Assets, Children, Commands, Entity, Mesh, Mesh3d, MeshMaterial3d, MessageReader,
+            Query, Res, ResMut, Transform, Vec3, Visibility,
         },
         tasks::{futures::check_ready, AsyncComputeTaskPool},
     },
-    bevycraft_core::prelude::Block,
-    bevycraft_world::prelude::{ChunkMap, ChunkPos, ChunkReady, ChunkUnloaded, CHUNK_SIZE},
+    bevycraft_core::prelude::{Block, BlockStates},
+    bevycraft_world::prelude::{
+        ChunkMap, ChunkPos, ChunkReady, ChunkUnloaded, FallingBlock, CHUNK_SIZE,
+    },
 };
 
 pub fn trigger_chunk_meshing(
@@ -229,3 +231,43 @@ pub fn remesh_dirty_chunks(mut queue: ResMut<MeshingQueue>, mut chunk_map: ResMu
         queue.pending.insert(pos);
     }
 }
+
+/// Gives newly spawned falling blocks the mesh of their block model.
+pub fn mesh_falling_blocks(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    falling: Query<(Entity, &FallingBlock), Added<FallingBlock>>,
+    model_cache: Res<ModelCache<Block, BlockModel>>,
+    textures: Res<ArrayTexture>,
+) {
+    let states = BlockStates::global();
+
+    for (entity, block) in &falling {
+        let Some(model) = model_cache.get(states.block_of(block.state)) else {
+            continue;
+        };
+
+        let output = mesh_block_model(model);
+
+        for (mesh_opt, mode) in [
+            (output.opaque, RenderMode::Opaque),
+            (output.cutout, RenderMode::Cutout),
+            (output.translucent, RenderMode::Translucent),
+        ] {
+            let Some(mesh) = mesh_opt else {
+                continue;
+            };
+
+            let child = commands
+                .spawn((
+                    Mesh3d(meshes.add(mesh)),
+                    MeshMaterial3d(textures.get_vertex_material(mode)),
+                    Transform::default(),
+                    Visibility::default(),
+                ))
+                .id();
+
+            commands.entity(entity).add_child(child);
+        }
+    }
+}
@@ -0,0 +1,91 @@
+use {
+    crate::chunk::storage::{delinearize, linearize},
+    bevy::{math::IVec3, platform::collections::HashMap},
+    bevycraft_core::prelude::BlockEntity,
+    std::fmt::{Debug, Formatter},
+};
+
+/// Block entities of a chunk, keyed by the local index of their block.
+#[derive(Default)]
+pub struct BlockEntityMap {
+    entries: HashMap<u16, Box<dyn BlockEntity>>,
+}
+
+impl Clone for BlockEntityMap {
+    fn clone(&self) -> Self {
+        Self {
+            entries: self
+                .entries
+                .iter()
+                .map(|(&index, entity)| (index, (**entity).clone_boxed()))
+                .collect(),
+        }
+    }
+}
+
+impl Debug for BlockEntityMap {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_map().entries(self.entries.iter()).finish()
+    }
+}
+
+impl BlockEntityMap {
+    #[inline]
+    pub fn get(&self, position: IVec3) -> Option<&dyn BlockEntity> {
+        self.entries.get(&local_index(position)).map(|entity| &**entity)
+    }
+
+    #[inline]
+    pub fn get_mut(&mut self, position: IVec3) -> Option<&mut dyn BlockEntity> {
+        self.entries
+            .get_mut(&local_index(position))
+            .map(|entity| &mut **entity)
+    }
+
+    #[inline]
+    pub fn insert(
+        &mut self,
+        position: IVec3,
+        entity: Box<dyn BlockEntity>,
+    ) -> Option<Box<dyn BlockEntity>> {
+        self.entries.insert(local_index(position), entity)
+    }
+
+    #[inline]
+    pub fn remove(&mut self, position: IVec3) -> Option<Box<dyn BlockEntity>> {
+        self.entries.remove(&local_index(position))
+    }
+
+    #[inline]
+    pub fn retain(&mut self, mut f: impl FnMut(IVec3, &mut dyn BlockEntity) -> bool) {
+        self.entries
+            .retain(|&index, entity| f(local_position(index), &mut **entity));
+    }
+
+    #[inline]
+    pub fn iter(&self) -> impl Iterator<Item = (IVec3, &dyn BlockEntity)> {
+        self.entries
+            .iter()
+            .map(|(&index, entity)| (local_position(index), &**entity))
+    }
+
+    #[inline(always)]
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    #[inline(always)]
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+#[inline(always)]
+fn local_index(position: IVec3) -> u16 {
+    linearize(position) as u16
+}
+
+#[inline(always)]
+fn local_position(index: u16) -> IVec3 {
+    delinearize(index as usize)
+}
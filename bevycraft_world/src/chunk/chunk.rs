@@ -4,12 +4,15 @@ use {
         ecs::component::Component,
         math::{bounding::Aabb3d, IVec3, Vec3},
     },
-    bevycraft_core::blocks::AIR,
+    bevycraft_core::{
+        blocks::AIR,
+        prelude::{BlockEntity, BlockEntityType, BlockStates, IdRemap, RemapError},
+    },
     std::{
         fmt::{Debug, Display, Formatter, Result},
         hash::{Hash, Hasher},
         mem::transmute,
-        ops::{Add, Div, Mul, Sub},
+        ops::{Add, Div, Mul, Range, Sub},
         sync::Arc,
     },
 };
@@ -18,10 +21,21 @@ pub const CHUNK_SIZE: i32 = 16;
 
 pub const CHUNK_LEN: usize = (CHUNK_SIZE * CHUNK_SIZE * CHUNK_SIZE) as usize;
 
+/// Blocks, block entities and light of a chunk.
+///
+/// Edits keep the block entities and light in sync through the global [`BlockStates`], so they
+/// need it built or installed first.
 #[derive(Component)]
 pub struct Chunk {
     pub storage: Arc<ChunkStorage>,
 
+    pub block_entities: Arc<BlockEntityMap>,
+
+    pub light: Arc<ChunkLight>,
+
+    /// Local positions whose light must be updated, drained by [`update_light`].
+    pub(crate) light_updates: Vec<IVec3>,
+
     pub dirty: bool,
 }
 
@@ -30,31 +44,135 @@ impl Chunk {
     pub fn empty() -> Self {
         Self {
             storage: Arc::new(ChunkStorage::Empty),
+            block_entities: Arc::default(),
+            light: Arc::default(),
+            light_updates: Vec::new(),
             dirty: false,
         }
     }
 
     #[inline]
-    pub fn uniform(block: usize) -> Self {
+    pub fn uniform(state: usize) -> Self {
         Self {
-            storage: Arc::new(ChunkStorage::Single(block)),
+            storage: Arc::new(ChunkStorage::Single(state)),
+            block_entities: Arc::default(),
+            light: Arc::default(),
+            light_updates: Vec::new(),
             dirty: false,
         }
     }
 
     #[inline]
-    pub fn set(&mut self, position: impl Into<IVec3>, block: usize) {
+    pub fn set(&mut self, position: impl Into<IVec3>, state: usize) {
         let position = position.into();
 
         if !check_bounds(position) {
             return;
         }
 
-        Arc::make_mut(&mut self.storage).set(position, block.into());
+        let previous = self.storage.get(position);
+
+        Arc::make_mut(&mut self.storage).set(position, state);
+        self.sync_block_entity(position, previous, state);
+        self.queue_light_update(position, previous, state);
+
+        self.dirty = true;
+    }
+
+    /// Sets the states in `range` of storage indices, which run along `x`, then `z`, then `y`.
+    /// Indices past the end of the chunk are skipped.
+    pub fn fill_range(&mut self, range: Range<usize>, state: usize) {
+        let range = range.start..range.end.min(CHUNK_LEN);
+
+        self.write_bulk(range.clone().map(delinearize).collect(), |storage| {
+            storage.fill_range(range, state)
+        });
+    }
+
+    /// Sets every position in the box from `min` to `max`, both inclusive, to `state`. Positions
+    /// outside the chunk are skipped.
+    pub fn fill_box(&mut self, min: impl Into<IVec3>, max: impl Into<IVec3>, state: usize) {
+        let Some((min, max)) = clamp_box(min.into(), max.into()) else {
+            return;
+        };
+
+        self.write_bulk(box_positions(min, max).collect(), |storage| {
+            storage.fill_box(min, max, state)
+        });
+    }
+
+    /// Copies the blocks of `other` in the box from `src_min` to `src_max`, both inclusive, to the
+    /// same positions moved by `dst_offset`. Positions outside either chunk are skipped.
+    ///
+    /// Only the states are copied: copied blocks get new block entities.
+    pub fn copy_from(
+        &mut self,
+        other: &Chunk,
+        src_min: impl Into<IVec3>,
+        src_max: impl Into<IVec3>,
+        dst_offset: impl Into<IVec3>,
+    ) {
+        let dst_offset = dst_offset.into();
+
+        let Some((min, max)) = clamp_box(
+            src_min.into().max(-dst_offset),
+            src_max.into().min(IVec3::splat(CHUNK_SIZE - 1) - dst_offset),
+        ) else {
+            return;
+        };
+
+        let positions = box_positions(min + dst_offset, max + dst_offset).collect();
+
+        self.write_bulk(positions, |storage| {
+            storage.copy_from(&other.storage, min, max, dst_offset)
+        });
+    }
+
+    /// Sets the state at each position, the last one winning for repeated positions. Positions
+    /// outside the chunk are skipped.
+    pub fn set_many(&mut self, states: impl IntoIterator<Item = (IVec3, usize)>) {
+        let states: Vec<(IVec3, usize)> = states
+            .into_iter()
+            .filter(|&(position, _)| check_bounds(position))
+            .collect();
+
+        let mut positions: Vec<IVec3> = states.iter().map(|&(position, _)| position).collect();
+
+        positions.sort_unstable_by_key(|&position| linearize(position));
+        positions.dedup();
+
+        self.write_bulk(positions, |storage| storage.set_many(states));
+    }
+
+    /// Runs a bulk `write` on the storage, then syncs the block entities and light of every
+    /// position in `positions`, which must be unique and cover every position written.
+    fn write_bulk(&mut self, positions: Vec<IVec3>, write: impl FnOnce(&mut ChunkStorage)) {
+        if positions.is_empty() {
+            return;
+        }
+
+        let previous: Vec<usize> = positions
+            .iter()
+            .map(|&position| self.storage.get(position))
+            .collect();
+
+        write(Arc::make_mut(&mut self.storage));
+
+        for (position, previous) in positions.into_iter().zip(previous) {
+            let state = self.storage.get(position);
+
+            if state != previous {
+                self.sync_block_entity(position, previous, state);
+                self.queue_light_update(position, previous, state);
+            }
+        }
 
         self.dirty = true;
     }
 
+    /// Replaces the block at `position` with air, returning the removed state.
+    ///
+    /// This skips callbacks and loot; break blocks through [`WorldAccess`] to have them drop.
     #[inline]
     pub fn remove(&mut self, position: impl Into<IVec3>) -> Option<usize> {
         let position = position.into();
@@ -65,13 +183,83 @@ impl Chunk {
 
         let removed = self.storage.get(position);
 
-        Arc::make_mut(&mut self.storage).set(position, *AIR);
+        Arc::make_mut(&mut self.storage).set(position, AIR.default_state());
+        self.sync_block_entity(position, removed, AIR.default_state());
+        self.queue_light_update(position, removed, AIR.default_state());
 
         self.dirty = true;
 
         Some(removed)
     }
 
+    #[inline]
+    pub fn block_entity<T: BlockEntity>(&self, position: impl Into<IVec3>) -> Option<&T> {
+        let position = position.into();
+
+        if !check_bounds(position) {
+            return None;
+        }
+
+        self.block_entities.get(position)?.downcast_ref()
+    }
+
+    /// Mutably borrows a block entity, unsharing the block entities of this chunk first.
+    #[inline]
+    pub fn block_entity_mut<T: BlockEntity>(
+        &mut self,
+        position: impl Into<IVec3>,
+    ) -> Option<&mut T> {
+        let position = position.into();
+
+        if !check_bounds(position) || !self.block_entities.get(position)?.is::<T>() {
+            return None;
+        }
+
+        Arc::make_mut(&mut self.block_entities)
+            .get_mut(position)?
+            .downcast_mut()
+    }
+
+    /// Creates or destroys the block entity at `position` when its block changes.
+    fn sync_block_entity(&mut self, position: IVec3, previous: usize, state: usize) {
+        let states = BlockStates::global();
+        let (before, after) = (states.block_of(previous), states.block_of(state));
+
+        if before == after {
+            return;
+        }
+
+        if states.block_entity_of(before).is_some() {
+            Arc::make_mut(&mut self.block_entities).remove(position);
+        }
+
+        if let Some(block_entity) = states.block_entity_of(after) {
+            Arc::make_mut(&mut self.block_entities).insert(position, block_entity.create());
+        }
+    }
+
+    /// Queues `position` for a light update when its new block emits or blocks light differently.
+    fn queue_light_update(&mut self, position: IVec3, previous: usize, state: usize) {
+        let states = BlockStates::global();
+
+        if states.light_emission(previous) != states.light_emission(state)
+            || states.light_opacity(previous) != states.light_opacity(state)
+        {
+            self.light_updates.push(position);
+        }
+    }
+
+    #[inline]
+    pub fn light(&self, position: impl Into<IVec3>) -> Option<LightLevel> {
+        let position = position.into();
+
+        if !check_bounds(position) {
+            return None;
+        }
+
+        Some(self.light.sample(position))
+    }
+
     #[inline]
     pub fn get(&self, position: impl Into<IVec3>) -> Option<usize> {
         let position = position.into();
@@ -83,6 +271,46 @@ impl Chunk {
         Some(self.storage.get(position))
     }
 
+    /// Remaps every state through `remap`, then syncs the block entities like [`Self::set`]:
+    /// blocks that no longer have one lose it, and blocks that now need one get a new one.
+    pub fn remap(&mut self, remap: &IdRemap) -> std::result::Result<(), RemapError> {
+        Arc::make_mut(&mut self.storage).remap(remap)?;
+
+        let states = BlockStates::global();
+
+        if !self.block_entities.is_empty() {
+            let storage = &self.storage;
+
+            Arc::make_mut(&mut self.block_entities).retain(|position, _| {
+                let block = states.block_of(storage.get(position));
+
+                states.block_entity_of(block).is_some()
+            });
+        }
+
+        let missing: Vec<(IVec3, BlockEntityType)> = self
+            .iter_with_position()
+            .filter(|&(position, _)| self.block_entities.get(position).is_none())
+            .filter_map(|(position, state)| {
+                let block_entity = states.block_entity_of(states.block_of(state))?;
+
+                Some((position, *block_entity))
+            })
+            .collect();
+
+        if !missing.is_empty() {
+            let block_entities = Arc::make_mut(&mut self.block_entities);
+
+            for (position, block_entity) in missing {
+                block_entities.insert(position, block_entity.create());
+            }
+        }
+
+        self.dirty = true;
+
+        Ok(())
+    }
+
     #[inline]
     pub fn iter(&self) -> impl Iterator<Item = usize> {
         self.storage.iter()
@@ -90,12 +318,12 @@ impl Chunk {
 
     #[inline]
     pub fn iter_with_position(&self) -> impl Iterator<Item = (IVec3, usize)> {
-        self.storage.iter().enumerate().map(|(i, block)| {
+        self.storage.iter().enumerate().map(|(i, state)| {
             let x = (i & 0xF) as i32;
             let z = ((i >> 4) & 0xF) as i32;
             let y = (i >> 8) as i32;
 
-            (IVec3::new(x, y, z), block)
+            (IVec3::new(x, y, z), state)
         })
     }
 }
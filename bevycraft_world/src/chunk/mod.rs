@@ -1,3 +1,4 @@
+pub mod block_entities;
 pub mod chunk;
 pub mod storage;
 pub mod map;
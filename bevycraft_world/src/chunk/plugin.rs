@@ -4,7 +4,10 @@ use {
             poll_chunk_tasks, process_unload_queue, spawn_chunk_tasks, update_queue, ChunkReady,
             ChunkUnloaded, ViewVolume,
         },
-        prelude::{ChunkGenerator, ChunkLoaderConfig, ChunkMap, GeneratorResource},
+        prelude::{
+            tick_blocks, update_falling_blocks, update_light, BlockDrops, ChunkGenerator,
+            ChunkLoaderConfig, ChunkMap, GeneratorResource, TickConfig, TickScheduler,
+        },
     },
     bevy::{
         app::{App, Plugin},
@@ -17,6 +20,11 @@ pub enum ChunkSet {
     Schedule,
     Dispatch,
     Integrate,
+    /// Runs scheduled and random block ticks, then moves falling blocks. Blocks broken on the
+    /// way are reported as [`BlockDrops`].
+    Tick,
+    /// Lights newly loaded chunks and applies light updates queued by block changes.
+    Light,
     Cleanup,
 }
 
@@ -60,14 +68,19 @@ impl<S: States> Plugin for ChunkPlugin<S> {
                 unload_margin: 2,
             })
             .insert_resource(ViewVolume::new())
+            .init_resource::<TickScheduler>()
+            .init_resource::<TickConfig>()
             .add_message::<ChunkReady>()
             .add_message::<ChunkUnloaded>()
+            .add_message::<BlockDrops>()
             .configure_sets(
                 FixedUpdate,
                 (
                     ChunkSet::Schedule,
                     ChunkSet::Dispatch,
                     ChunkSet::Integrate,
+                    ChunkSet::Tick,
+                    ChunkSet::Light,
                     ChunkSet::Cleanup,
                 )
                     .chain(),
@@ -78,6 +91,10 @@ impl<S: States> Plugin for ChunkPlugin<S> {
                     update_queue.in_set(ChunkSet::Schedule),
                     spawn_chunk_tasks.in_set(ChunkSet::Dispatch),
                     poll_chunk_tasks.in_set(ChunkSet::Integrate),
+                    (tick_blocks, update_falling_blocks)
+                        .chain()
+                        .in_set(ChunkSet::Tick),
+                    update_light.in_set(ChunkSet::Light),
                     process_unload_queue.in_set(ChunkSet::Cleanup),
                 )
                     .run_if(in_state(self.run_in_state.clone())),
@@ -1,14 +1,22 @@
 use {
-    crate::prelude::{CHUNK_LEN, CHUNK_SIZE},
+    crate::prelude::{check_bounds, CHUNK_LEN, CHUNK_SIZE},
     bevy::math::IVec3,
     bevycraft_core::{
         blocks::AIR,
-        prelude::{PatternContainer, PatternIter},
+        prelude::{IdRemap, PatternContainer, PatternIter, RemapError},
+    },
+    serde::{Deserialize, Serialize},
+    std::{
+        iter::{Repeat, Take},
+        ops::Range,
     },
-    std::iter::{Repeat, Take},
 };
 
-#[derive(Debug, Clone)]
+/// Serializes with the pattern of a [`ChunkStorage::Pattern`] packed, see [`PatternContainer`].
+///
+/// Empty storage stands for air, so most methods read the global
+/// [`BlockStates`](bevycraft_core::prelude::BlockStates) and need it built or installed first.
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum ChunkStorage {
     Empty,
     Single(usize),
@@ -18,48 +26,146 @@ pub enum ChunkStorage {
 impl ChunkStorage {
     #[inline]
     pub fn empty_pattern() -> Self {
-        Self::Pattern(PatternContainer::new(*AIR))
+        Self::Pattern(PatternContainer::new(AIR.default_state()))
     }
 
     #[inline]
     pub fn get(&self, position: IVec3) -> usize {
         match self {
-            Self::Empty => *AIR,
+            Self::Empty => AIR.default_state(),
             Self::Single(b) => *b,
             Self::Pattern(p) => {
                 let idx = linearize(position);
 
-                p.get(idx).copied().unwrap_or(*AIR)
+                p.get(idx).copied().unwrap_or(AIR.default_state())
             }
         }
     }
 
     #[inline]
-    pub fn set(&mut self, position: IVec3, block: usize) {
-        let idx = linearize(position);
+    pub fn set(&mut self, position: IVec3, state: usize) {
+        self.pattern_mut().set(linearize(position), state);
+    }
 
-        match self {
-            Self::Empty => {
-                let mut container = PatternContainer::new(*AIR);
+    #[inline]
+    pub fn fill(&mut self, state: usize) {
+        *self = Self::Single(state);
+    }
 
-                container.set(idx, block);
+    /// Sets the states in `range` of storage indices, which run along `x`, then `z`, then `y`.
+    /// Indices past the end of the chunk are skipped.
+    pub fn fill_range(&mut self, range: Range<usize>, state: usize) {
+        let range = range.start..range.end.min(CHUNK_LEN);
 
-                *self = Self::Pattern(container);
-            }
-            Self::Single(b) => {
-                let mut container = PatternContainer::new(*b);
+        if range.is_empty() || self.is_filled_with(state) {
+            return;
+        }
+
+        if range.start == 0 && range.end == CHUNK_LEN {
+            self.fill_whole(state);
+
+            return;
+        }
+
+        self.pattern_mut().fill_range(range, state);
+    }
+
+    /// Sets the states in the box from `min` to `max`, both inclusive. Positions outside the
+    /// chunk are skipped.
+    pub fn fill_box(&mut self, min: IVec3, max: IVec3, state: usize) {
+        let Some((min, max)) = clamp_box(min, max) else {
+            return;
+        };
+
+        if self.is_filled_with(state) {
+            return;
+        }
 
-                container.set(idx, block);
+        if min == IVec3::ZERO && max == IVec3::splat(CHUNK_SIZE - 1) {
+            self.fill_whole(state);
 
-                *self = Self::Pattern(container);
+            return;
+        }
+
+        self.pattern_mut()
+            .fill_indices(box_positions(min, max).map(linearize), state);
+    }
+
+    /// Copies the states of `other` in the box from `src_min` to `src_max`, both inclusive, to
+    /// the same positions moved by `dst_offset`. Positions outside either chunk are skipped.
+    pub fn copy_from(
+        &mut self,
+        other: &ChunkStorage,
+        src_min: IVec3,
+        src_max: IVec3,
+        dst_offset: IVec3,
+    ) {
+        let Some((min, max)) = clamp_box(
+            src_min.max(-dst_offset),
+            src_max.min(IVec3::splat(CHUNK_SIZE - 1) - dst_offset),
+        ) else {
+            return;
+        };
+
+        match other {
+            Self::Pattern(source) => {
+                let pairs = box_positions(min, max)
+                    .map(|position| (linearize(position), linearize(position + dst_offset)));
+
+                self.pattern_mut().copy_indices_from(source, pairs);
             }
-            Self::Pattern(p) => p.set(idx, block),
+            _ => self.fill_box(min + dst_offset, max + dst_offset, other.get(IVec3::ZERO)),
         }
     }
 
+    /// Sets the state at each position, the last one winning for repeated positions. Positions
+    /// outside the chunk are skipped.
+    pub fn set_many(&mut self, states: impl IntoIterator<Item = (IVec3, usize)>) {
+        let mut states = states
+            .into_iter()
+            .filter(|&(position, _)| check_bounds(position))
+            .peekable();
+
+        if states.peek().is_none() {
+            return;
+        }
+
+        self.pattern_mut()
+            .set_many(states.map(|(position, state)| (linearize(position), state)));
+    }
+
+    /// Makes this storage hold `state` everywhere, as [`Self::Empty`] for air.
     #[inline]
-    pub fn fill(&mut self, block: usize) {
-        *self = Self::Single(block);
+    fn fill_whole(&mut self, state: usize) {
+        if state == AIR.default_state() {
+            self.clear();
+        } else {
+            self.fill(state);
+        }
+    }
+
+    #[inline]
+    fn is_filled_with(&self, state: usize) -> bool {
+        match self {
+            Self::Empty => state == AIR.default_state(),
+            Self::Single(b) => *b == state,
+            Self::Pattern(_) => false,
+        }
+    }
+
+    /// The pattern of this storage, spreading an empty or uniform storage into one first.
+    fn pattern_mut(&mut self) -> &mut PatternContainer<usize, CHUNK_LEN> {
+        match self {
+            Self::Empty => *self = Self::empty_pattern(),
+            Self::Single(b) => *self = Self::Pattern(PatternContainer::new(*b)),
+            Self::Pattern(_) => {}
+        }
+
+        let Self::Pattern(p) = self else {
+            unreachable!()
+        };
+
+        p
     }
 
     pub fn clear(&mut self) {
@@ -71,7 +177,7 @@ impl ChunkStorage {
         match self {
             Self::Pattern(p) => {
                 if let Some(single) = p.as_single().copied() {
-                    if single == *AIR {
+                    if single == AIR.default_state() {
                         *self = Self::Empty;
                     } else {
                         *self = Self::Single(single);
@@ -86,10 +192,34 @@ impl ChunkStorage {
         }
     }
 
+    /// Converts every stored state id through `remap`, e.g. after loading chunk data saved
+    /// under a different registry layout.
+    pub fn remap(&mut self, remap: &IdRemap) -> Result<(), RemapError> {
+        match self {
+            Self::Empty => {}
+            Self::Single(s) => *s = remap.apply(*s)?,
+            Self::Pattern(p) => {
+                let mut states = Box::new([0usize; CHUNK_LEN]);
+
+                p.unpack_into(&mut states);
+
+                for state in states.iter_mut() {
+                    *state = remap.apply(*state)?;
+                }
+
+                *p = PatternContainer::from_dense_with_layout(&states, p.layout());
+            }
+        }
+
+        Ok(())
+    }
+
     #[inline]
     pub fn iter(&self) -> ChunkIter<'_> {
         match self {
-            Self::Empty => ChunkIter::Uniform(std::iter::repeat(*AIR).take(CHUNK_LEN)),
+            Self::Empty => {
+                ChunkIter::Uniform(std::iter::repeat(AIR.default_state()).take(CHUNK_LEN))
+            }
             Self::Single(b) => ChunkIter::Uniform(std::iter::repeat(*b).take(CHUNK_LEN)),
             Self::Pattern(p) => ChunkIter::Pattern(p.iter()),
         }
@@ -145,7 +275,36 @@ impl Iterator for ChunkIter<'_> {
     }
 }
 
+/// Clamps the box from `min` to `max`, both inclusive, to the chunk, or `None` if it lies
+/// outside.
+#[inline]
+pub(crate) fn clamp_box(min: IVec3, max: IVec3) -> Option<(IVec3, IVec3)> {
+    let min = min.max(IVec3::ZERO);
+    let max = max.min(IVec3::splat(CHUNK_SIZE - 1));
+
+    min.cmple(max).all().then_some((min, max))
+}
+
+/// Positions in the box from `min` to `max`, both inclusive, in storage order.
+#[inline]
+pub(crate) fn box_positions(min: IVec3, max: IVec3) -> impl Iterator<Item = IVec3> {
+    (min.y..=max.y).flat_map(move |y| {
+        (min.z..=max.z).flat_map(move |z| (min.x..=max.x).map(move |x| IVec3::new(x, y, z)))
+    })
+}
+
 #[inline(always)]
-const fn linearize(position: IVec3) -> usize {
+pub(crate) const fn linearize(position: IVec3) -> usize {
     (position.x + (position.z * CHUNK_SIZE) + (position.y * CHUNK_SIZE * CHUNK_SIZE)) as usize
 }
+
+#[inline(always)]
+pub(crate) const fn delinearize(index: usize) -> IVec3 {
+    let index = index as i32;
+
+    IVec3::new(
+        index % CHUNK_SIZE,
+        index / (CHUNK_SIZE * CHUNK_SIZE),
+        (index / CHUNK_SIZE) % CHUNK_SIZE,
+    )
+}
@@ -0,0 +1,120 @@
+use {
+    crate::prelude::{split_world_pos, ChunkPos, WorldAccess},
+    bevy::{
+        math::{IVec3, Vec3},
+        platform::collections::{HashMap, HashSet},
+    },
+    bevycraft_core::{
+        blocks::AIR,
+        prelude::{BlockFlags, BlockLevel, BlockStates},
+    },
+};
+
+/// Rays cast along each edge of the cube of ray directions.
+const RAYS_PER_EDGE: i32 = 16;
+
+/// Distance a ray advances per step, in blocks.
+const RAY_STEP: f32 = 0.3;
+
+/// Intensity a ray loses per step on its own, so it fades out even through air.
+const RAY_DECAY: f32 = 0.225;
+
+/// Toughness added to every block a ray passes, so even the weakest blocks slow it down.
+const BASE_RESISTANCE: f32 = 0.3;
+
+/// Blows up the blocks around `center`, returning the positions and states of the destroyed
+/// blocks. They are also recorded as broken, so their loot drops once
+/// [`WorldAccess::take_broken`] is drained.
+///
+/// Rays are cast from `center` in all directions, each starting with an intensity of about
+/// `power` and weakened by the toughness of the blocks it passes. A block is destroyed once any
+/// ray still has intensity left after passing it. Fluids slow rays down like any other block
+/// but are never destroyed. Rays stop at unloaded chunks.
+///
+/// The destroyed blocks are removed chunk by chunk, then their neighbors are notified. Blocks
+/// this leaves unsupported, like sand or gravel, are only queued to fall; the caller must drain
+/// [`WorldAccess::take_falling`] into
+/// [`spawn_falling_blocks`](crate::prelude::spawn_falling_blocks) or they vanish.
+///
+/// Reads the toughness of blocks from the global [`BlockStates`], which must be built or
+/// installed.
+pub fn explode(level: &mut WorldAccess, center: Vec3, power: f32) -> Vec<(IVec3, usize)> {
+    let states = BlockStates::global();
+    let air = AIR.default_state();
+
+    let mut seen = HashSet::new();
+    let mut destroyed = Vec::new();
+
+    for direction in ray_directions() {
+        let mut intensity = power * (0.7 + 0.6 * level.scheduler.rng.f32());
+        let mut point = center;
+
+        while intensity > 0.0 {
+            let position = point.floor().as_ivec3();
+
+            let Some(state) = level.get_state(position) else {
+                break;
+            };
+
+            if state != air {
+                intensity -= (states.toughness(state) + BASE_RESISTANCE) * RAY_STEP;
+
+                let fluid = states
+                    .flags_of(states.block_of(state))
+                    .contains(BlockFlags::FLUID);
+
+                if intensity > 0.0 && !fluid && seen.insert(position) {
+                    destroyed.push((position, state));
+                }
+            }
+
+            point += direction * RAY_STEP;
+            intensity -= RAY_DECAY;
+        }
+    }
+
+    for &(position, state) in &destroyed {
+        if let Some(callbacks) = states.callbacks_of(states.block_of(state)) {
+            callbacks.on_break(level, position, state);
+        }
+    }
+
+    let mut chunks: HashMap<ChunkPos, Vec<IVec3>> = HashMap::new();
+
+    for &(position, _) in &destroyed {
+        let (chunk, local) = split_world_pos(position);
+
+        chunks.entry(chunk).or_default().push(local);
+    }
+
+    for (pos, locals) in chunks {
+        let Some(chunk) = level.chunk_map.get_mut(&pos) else {
+            continue;
+        };
+
+        chunk.set_many(locals.into_iter().map(|local| (local, air)));
+    }
+
+    level.broken.extend_from_slice(&destroyed);
+
+    for &(position, _) in &destroyed {
+        level.notify_neighbors(position);
+    }
+
+    destroyed
+}
+
+/// Unit directions towards the points on the surface of a cube, spread evenly along its faces.
+fn ray_directions() -> impl Iterator<Item = Vec3> {
+    const LAST: i32 = RAYS_PER_EDGE - 1;
+
+    (0..RAYS_PER_EDGE).flat_map(|x| {
+        (0..RAYS_PER_EDGE).flat_map(move |y| {
+            (0..RAYS_PER_EDGE)
+                .filter(move |&z| [x, y, z].iter().any(|&c| c == 0 || c == LAST))
+                .map(move |z| {
+                    (IVec3::new(x, y, z).as_vec3() / LAST as f32 * 2.0 - 1.0).normalize()
+                })
+        })
+    })
+}
@@ -0,0 +1,39 @@
+use {
+    bevy::prelude::{Component, Transform, Visibility},
+    bevycraft_core::prelude::{BlockFlags, BlockStates},
+};
+
+/// Downwards acceleration of falling blocks, in blocks per second squared.
+pub const GRAVITY: f32 = 32.0;
+
+/// Highest speed a falling block reaches, in blocks per second.
+pub const TERMINAL_VELOCITY: f32 = 48.0;
+
+/// A block that lost its support and falls until it lands, placing itself back into the world.
+///
+/// The [`Transform`] holds the minimum corner of the block, like the blocks in a chunk.
+#[derive(Component, Debug, Clone, Copy, PartialEq)]
+#[require(Transform, Visibility)]
+pub struct FallingBlock {
+    pub state: usize,
+    /// Current downwards speed, in blocks per second.
+    pub velocity: f32,
+}
+
+impl FallingBlock {
+    #[inline]
+    pub const fn new(state: usize) -> Self {
+        Self {
+            state,
+            velocity: 0.0,
+        }
+    }
+}
+
+/// Whether a falling block passes through `state` instead of landing on it.
+#[inline]
+pub fn can_fall_into(states: &BlockStates, state: usize) -> bool {
+    states
+        .flags_of(states.block_of(state))
+        .intersects(BlockFlags::AIR | BlockFlags::REPLACEABLE)
+}
@@ -0,0 +1,98 @@
+use {
+    crate::prelude::{
+        can_fall_into, emit_block_drops, BlockDrops, ChunkMap, FallingBlock, TickScheduler,
+        WorldAccess, GRAVITY, TERMINAL_VELOCITY,
+    },
+    bevy::{
+        math::IVec3,
+        prelude::{Commands, Entity, MessageWriter, Query, Res, ResMut, Time, Transform},
+    },
+    bevycraft_core::{
+        blocks::AIR,
+        prelude::{BlockLevel, BlockStates, LootTables},
+    },
+};
+
+/// Moves the falling blocks down, placing them back into the world once they land.
+///
+/// A block stops above unloaded chunks and waits for them to load. Replaceable blocks in the cell
+/// it lands in are broken, and if that cell got filled in the meantime, the falling block breaks
+/// instead. Either way the broken block drops its loot.
+///
+/// Runs after [`RegistrySet::Freeze`](bevycraft_core::prelude::RegistrySet::Freeze), which
+/// builds the global [`BlockStates`] it reads.
+pub fn update_falling_blocks(
+    mut commands: Commands,
+    mut chunk_map: ResMut<ChunkMap>,
+    mut scheduler: ResMut<TickScheduler>,
+    mut falling: Query<(Entity, &mut FallingBlock, &mut Transform)>,
+    mut drops_msg: MessageWriter<BlockDrops>,
+    time: Res<Time>,
+    loot: Option<Res<LootTables>>,
+) {
+    let states = BlockStates::global();
+    let delta = time.delta_secs();
+
+    let mut level = WorldAccess::new(&mut chunk_map, &mut scheduler);
+
+    for (entity, mut block, mut transform) in &mut falling {
+        let position = transform.translation.floor().as_ivec3();
+
+        block.velocity = (block.velocity + GRAVITY * delta).min(TERMINAL_VELOCITY);
+
+        let target = transform.translation.y - block.velocity * delta;
+
+        // The first cell entered by the bottom of the block this step that stops it.
+        let blocked = (target.floor() as i32..position.y)
+            .rev()
+            .map(|y| IVec3::new(position.x, y, position.z))
+            .map(|cell| (cell, level.get_state(cell)))
+            .find(|&(_, state)| !state.is_some_and(|state| can_fall_into(states, state)));
+
+        let Some((cell, below)) = blocked else {
+            transform.translation.y = target;
+
+            continue;
+        };
+
+        // Wait above the unloaded chunk until it loads.
+        if below.is_none() {
+            transform.translation.y = (cell.y + 1) as f32;
+            block.velocity = 0.0;
+
+            continue;
+        }
+
+        let landing = cell + IVec3::Y;
+
+        match level.get_state(landing) {
+            Some(replaced) if can_fall_into(states, replaced) => {
+                if replaced != AIR.default_state() {
+                    level.broken.push((landing, replaced));
+                }
+
+                level.place_block(landing, block.state);
+            }
+            // The cell got filled in the meantime, so the block breaks instead of landing.
+            _ => level.broken.push((landing, block.state)),
+        }
+
+        commands.entity(entity).despawn();
+    }
+
+    spawn_falling_blocks(&mut commands, level.take_falling());
+
+    let broken = level.take_broken();
+    emit_block_drops(&mut drops_msg, loot.as_deref(), &mut level.scheduler.rng, broken);
+}
+
+/// Spawns the entities of blocks that just started falling, as taken from
+/// [`WorldAccess::take_falling`].
+pub fn spawn_falling_blocks(commands: &mut Commands, falling: Vec<(IVec3, usize)>) {
+    for (position, state) in falling {
+        commands.spawn((
+            FallingBlock::new(state),
+            Transform::from_translation(position.as_vec3()),
+        ));
+    }
+}
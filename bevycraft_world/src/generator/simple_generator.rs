@@ -64,11 +64,11 @@ impl ChunkGenerator for SimpleGenerator {
                     }
 
                     let block = if y == surface_height {
-                        *GRASS_BLOCK
+                        GRASS_BLOCK.default_state()
                     } else if y > surface_height - 3 {
-                        *DIRT
+                        DIRT.default_state()
                     } else {
-                        *STONE
+                        STONE.default_state()
                     };
 
                     chunk.set([x, y - world_height, z], block);
@@ -234,7 +234,7 @@ impl TerrainGenerator {
 
             if depth < 0 {
                 if col.biome == Biome::Ocean && world_y <= self.sea_level {
-                    chunk.set([lx, ly, lz], *WATER);
+                    chunk.set([lx, ly, lz], WATER.default_state());
                 } else if world_y == surf + 1 {
                     if let Some(id) = self.surface_decoration(col, world_x, world_z) {
                         chunk.set([lx, ly, lz], id);
@@ -255,13 +255,13 @@ impl TerrainGenerator {
         match depth {
             0 => {
                 if col.temperature < 0.3 {
-                    *GRAVEL
+                    GRAVEL.default_state()
                 } else {
-                    *SAND
+                    SAND.default_state()
                 }
             }
-            d if d < self.dirt_depth => *GRAVEL,
-            _ => *STONE,
+            d if d < self.dirt_depth => GRAVEL.default_state(),
+            _ => STONE.default_state(),
         }
     }
 
@@ -288,31 +288,31 @@ impl TerrainGenerator {
                 .wrapping_mul(1_274_126_177);
             let mut rng = fastrand::Rng::with_seed(seed);
             if rng.f32() < coverage {
-                return *SNOW_BLOCK;
+                return SNOW_BLOCK.default_state();
             }
         }
 
         match col.biome {
-            Biome::Mountain => *STONE,
+            Biome::Mountain => STONE.default_state(),
             Biome::Desert | Biome::Beach => match depth {
-                0 => *SAND,
-                d if d < self.dirt_depth => *SAND,
-                _ => *STONE,
+                0 => SAND.default_state(),
+                d if d < self.dirt_depth => SAND.default_state(),
+                _ => STONE.default_state(),
             },
             Biome::Tundra => match depth {
-                0 => *SNOW_BLOCK,
-                d if d < self.dirt_depth => *DIRT,
-                _ => *STONE,
+                0 => SNOW_BLOCK.default_state(),
+                d if d < self.dirt_depth => DIRT.default_state(),
+                _ => STONE.default_state(),
             },
             Biome::Taiga => match depth {
-                0 => *GRASS_BLOCK,
-                d if d < self.dirt_depth => *DIRT,
-                _ => *STONE,
+                0 => GRASS_BLOCK.default_state(),
+                d if d < self.dirt_depth => DIRT.default_state(),
+                _ => STONE.default_state(),
             },
             _ => match depth {
-                0 => *GRASS_BLOCK,
-                d if d < self.dirt_depth => *DIRT,
-                _ => *STONE,
+                0 => GRASS_BLOCK.default_state(),
+                d if d < self.dirt_depth => DIRT.default_state(),
+                _ => STONE.default_state(),
             },
         }
     }
@@ -328,8 +328,8 @@ impl TerrainGenerator {
                     .wrapping_mul(1_274_126_177);
                 let mut rng = fastrand::Rng::with_seed(seed);
                 match rng.u8(0..100) {
-                    0..=9 => Some(*GRASS),
-                    10..=12 => Some(*POPPY),
+                    0..=9 => Some(GRASS.default_state()),
+                    10..=12 => Some(POPPY.default_state()),
                     _ => None,
                 }
             }
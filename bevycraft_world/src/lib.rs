@@ -1,11 +1,21 @@
 mod chunk;
+mod explosion;
+mod falling;
 mod generator;
+mod light;
+mod loot;
 mod morton;
+mod tick;
 
 pub mod prelude {
     pub use crate::{
-        chunk::{chunk::*, map::*, plugin::*, storage::*, system::*},
+        chunk::{block_entities::*, chunk::*, map::*, plugin::*, storage::*, system::*},
+        explosion::blast::*,
+        falling::{entity::*, system::*},
         generator::{simple_generator::SimpleGenerator, terrain_generator::TerrainGenerator},
+        light::{engine::LightEngine, storage::*, system::*},
+        loot::system::*,
         morton::morton_3d::{Morton3D, MortonDecodable, MortonEncodable},
+        tick::{access::*, scheduler::*, system::*},
     };
 }
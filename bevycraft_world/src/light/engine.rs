@@ -0,0 +1,314 @@
+use {
+    crate::prelude::{split_world_pos, ChunkLight, ChunkMap, ChunkPos, LightChannel, CHUNK_SIZE},
+    bevy::{
+        math::IVec3,
+        platform::{collections::HashSet, hash::NoOpHash},
+    },
+    bevycraft_core::prelude::{BlockStates, Direction, MAX_LIGHT},
+    std::{collections::VecDeque, sync::Arc},
+};
+
+/// Propagates sky and block light across the loaded chunks of a [`ChunkMap`].
+///
+/// Light only spreads into loaded chunks; chunks pull the light of their neighbors in when they
+/// are lit. Sky light travels straight down without loss through fully transparent blocks, every
+/// other step loses one level, or the opacity of the block entered if higher.
+///
+/// Creating one panics unless the global [`BlockStates`] is built or installed.
+pub struct LightEngine<'a> {
+    chunk_map: &'a mut ChunkMap,
+    states: &'static BlockStates,
+    changed: HashSet<ChunkPos, NoOpHash>,
+}
+
+impl<'a> LightEngine<'a> {
+    #[inline]
+    pub fn new(chunk_map: &'a mut ChunkMap) -> Self {
+        Self {
+            chunk_map,
+            states: BlockStates::global(),
+            changed: HashSet::with_hasher(NoOpHash),
+        }
+    }
+
+    /// Computes the light of a freshly loaded chunk from its blocks and its loaded neighbors.
+    ///
+    /// Without a loaded chunk above, the chunk is lit as if open to the sky. If the chunk below
+    /// was lit that way, its sky light is recomputed now that something covers it.
+    pub fn light_chunk(&mut self, pos: ChunkPos) {
+        let open_sky = !self.chunk_map.is_loaded(&(pos + Direction::PosY.offset()));
+
+        let Some(chunk) = self.chunk_map.get_mut(&pos) else {
+            return;
+        };
+
+        chunk.light = Arc::new(ChunkLight {
+            open_sky,
+            ..ChunkLight::default()
+        });
+        chunk.light_updates.clear();
+
+        self.changed.insert(pos);
+
+        let origin: IVec3 = (pos * CHUNK_SIZE).into();
+
+        for channel in LightChannel::ALL {
+            let mut queue = VecDeque::new();
+
+            for y in 0..CHUNK_SIZE {
+                for z in 0..CHUNK_SIZE {
+                    for x in 0..CHUNK_SIZE {
+                        let world = origin + IVec3::new(x, y, z);
+
+                        if self.source(world, channel) > 0 {
+                            queue.push_back(world);
+                        }
+                    }
+                }
+            }
+
+            for dir in Direction::ALL {
+                if !self.chunk_map.is_loaded(&(pos + dir.offset())) {
+                    continue;
+                }
+
+                queue.extend(border(origin, dir).map(|world| world + dir.offset()));
+            }
+
+            self.propagate(channel, queue);
+        }
+
+        self.cover_chunk_below(pos);
+    }
+
+    /// Updates the light around `world` after the block there changed.
+    pub fn update_block(&mut self, world: IVec3) {
+        for channel in LightChannel::ALL {
+            let Some(level) = self.light_at(world, channel) else {
+                continue;
+            };
+
+            let mut removal = VecDeque::new();
+
+            if level > 0 {
+                self.set_light(world, channel, 0);
+                removal.push_back((world, level));
+            }
+
+            let mut refill = self.unpropagate(channel, removal);
+
+            refill.push_back(world);
+            refill.extend(Direction::ALL.map(|dir| world + dir.offset()));
+
+            self.propagate(channel, refill);
+        }
+    }
+
+    /// Marks every chunk whose light changed as dirty, so it gets remeshed.
+    pub fn finish(self) {
+        for pos in &self.changed {
+            if let Some(chunk) = self.chunk_map.get_mut(pos) {
+                chunk.dirty = true;
+            }
+        }
+    }
+
+    /// Removes the assumed open sky of the chunk below `pos`, now that `pos` covers it.
+    fn cover_chunk_below(&mut self, pos: ChunkPos) {
+        let below = pos + Direction::NegY.offset();
+
+        let Some(chunk) = self.chunk_map.get_mut(&below) else {
+            return;
+        };
+
+        if !chunk.light.open_sky {
+            return;
+        }
+
+        Arc::make_mut(&mut chunk.light).open_sky = false;
+
+        let origin: IVec3 = (below * CHUNK_SIZE).into();
+        let mut removal = VecDeque::new();
+
+        for world in border(origin, Direction::PosY) {
+            let level = self.light_at(world, LightChannel::Sky).unwrap_or(0);
+
+            if level > 0 {
+                self.set_light(world, LightChannel::Sky, 0);
+                removal.push_back((world, level));
+            }
+        }
+
+        let refill = self.unpropagate(LightChannel::Sky, removal);
+
+        self.propagate(LightChannel::Sky, refill);
+    }
+
+    /// Spreads light outwards from `queue`, raising each seed to its own source level first.
+    fn propagate(&mut self, channel: LightChannel, mut queue: VecDeque<IVec3>) {
+        for &world in &queue {
+            let source = self.source(world, channel);
+
+            if self.light_at(world, channel).is_some_and(|level| level < source) {
+                self.set_light(world, channel, source);
+            }
+        }
+
+        while let Some(world) = queue.pop_front() {
+            let Some(level) = self.light_at(world, channel) else {
+                continue;
+            };
+
+            if level <= 1 {
+                continue;
+            }
+
+            for dir in Direction::ALL {
+                let neighbor = world + dir.offset();
+
+                let Some(state) = self.state_at(neighbor) else {
+                    continue;
+                };
+
+                let spread = spread(channel, level, dir, self.states.light_opacity(state));
+
+                if self
+                    .light_at(neighbor, channel)
+                    .is_some_and(|current| current < spread)
+                {
+                    self.set_light(neighbor, channel, spread);
+                    queue.push_back(neighbor);
+                }
+            }
+        }
+    }
+
+    /// Clears the light that spread from the removed `(position, level)` pairs, returning the
+    /// positions whose light must spread again.
+    fn unpropagate(
+        &mut self,
+        channel: LightChannel,
+        mut queue: VecDeque<(IVec3, u8)>,
+    ) -> VecDeque<IVec3> {
+        let mut refill = VecDeque::new();
+
+        while let Some((world, level)) = queue.pop_front() {
+            for dir in Direction::ALL {
+                let neighbor = world + dir.offset();
+
+                let Some(current) = self.light_at(neighbor, channel) else {
+                    continue;
+                };
+
+                if current == 0 {
+                    continue;
+                }
+
+                let direct = channel == LightChannel::Sky
+                    && dir == Direction::NegY
+                    && level == MAX_LIGHT
+                    && current == MAX_LIGHT;
+
+                if current < level || direct {
+                    self.set_light(neighbor, channel, 0);
+                    queue.push_back((neighbor, current));
+
+                    if self.source(neighbor, channel) > 0 {
+                        refill.push_back(neighbor);
+                    }
+                } else {
+                    refill.push_back(neighbor);
+                }
+            }
+        }
+
+        refill
+    }
+
+    /// Light emitted at `world` on its own: the emission of its block, or the sky above the top
+    /// layer of a chunk lit as open to the sky.
+    fn source(&self, world: IVec3, channel: LightChannel) -> u8 {
+        let (pos, local) = split_world_pos(world);
+
+        let Some(chunk) = self.chunk_map.get(&pos) else {
+            return 0;
+        };
+
+        let state = chunk.storage.get(local);
+
+        match channel {
+            LightChannel::Block => self.states.light_emission(state),
+            LightChannel::Sky if chunk.light.open_sky && local.y == CHUNK_SIZE - 1 => spread(
+                channel,
+                MAX_LIGHT,
+                Direction::NegY,
+                self.states.light_opacity(state),
+            ),
+            LightChannel::Sky => 0,
+        }
+    }
+
+    #[inline]
+    fn state_at(&self, world: IVec3) -> Option<usize> {
+        let (pos, local) = split_world_pos(world);
+
+        self.chunk_map
+            .get(&pos)
+            .map(|chunk| chunk.storage.get(local))
+    }
+
+    #[inline]
+    fn light_at(&self, world: IVec3, channel: LightChannel) -> Option<u8> {
+        let (pos, local) = split_world_pos(world);
+
+        self.chunk_map
+            .get(&pos)
+            .map(|chunk| chunk.light.get(local, channel))
+    }
+
+    #[inline]
+    fn set_light(&mut self, world: IVec3, channel: LightChannel, level: u8) {
+        let (pos, local) = split_world_pos(world);
+
+        if let Some(chunk) = self.chunk_map.get_mut(&pos) {
+            Arc::make_mut(&mut chunk.light).set(local, channel, level);
+
+            self.changed.insert(pos);
+        }
+    }
+}
+
+/// Light left after moving from a position at `level` towards `direction` into a block with
+/// the given opacity.
+#[inline(always)]
+const fn spread(channel: LightChannel, level: u8, direction: Direction, opacity: u8) -> u8 {
+    if matches!(channel, LightChannel::Sky)
+        && matches!(direction, Direction::NegY)
+        && level == MAX_LIGHT
+        && opacity == 0
+    {
+        return MAX_LIGHT;
+    }
+
+    level.saturating_sub(if opacity > 1 { opacity } else { 1 })
+}
+
+/// World positions of the layer of the chunk at `origin` facing `direction`.
+fn border(origin: IVec3, direction: Direction) -> impl Iterator<Item = IVec3> {
+    const LAST: i32 = CHUNK_SIZE - 1;
+
+    (0..CHUNK_SIZE).flat_map(move |a| {
+        (0..CHUNK_SIZE).map(move |b| {
+            let local = match direction {
+                Direction::PosX => IVec3::new(LAST, a, b),
+                Direction::NegX => IVec3::new(0, a, b),
+                Direction::PosY => IVec3::new(a, LAST, b),
+                Direction::NegY => IVec3::new(a, 0, b),
+                Direction::PosZ => IVec3::new(a, b, LAST),
+                Direction::NegZ => IVec3::new(a, b, 0),
+            };
+
+            origin + local
+        })
+    })
+}
@@ -0,0 +1,3 @@
+pub mod engine;
+pub mod storage;
+pub mod system;
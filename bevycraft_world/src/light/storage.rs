@@ -0,0 +1,138 @@
+use {
+    crate::{chunk::storage::linearize, prelude::CHUNK_LEN},
+    bevy::math::IVec3,
+    bevycraft_core::prelude::MAX_LIGHT,
+};
+
+/// Light levels of a whole chunk, packed two 4-bit levels per byte.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct NibbleArray(Box<[u8; CHUNK_LEN / 2]>);
+
+impl NibbleArray {
+    #[inline]
+    pub fn new() -> Self {
+        Self::filled(0)
+    }
+
+    #[inline]
+    pub fn filled(level: u8) -> Self {
+        let level = level & 0xF;
+
+        Self(Box::new([level | (level << 4); CHUNK_LEN / 2]))
+    }
+
+    #[inline(always)]
+    pub fn get(&self, index: usize) -> u8 {
+        (self.0[index >> 1] >> ((index & 1) << 2)) & 0xF
+    }
+
+    #[inline(always)]
+    pub fn set(&mut self, index: usize, level: u8) {
+        let shift = (index & 1) << 2;
+        let byte = &mut self.0[index >> 1];
+
+        *byte = (*byte & !(0xF << shift)) | ((level & 0xF) << shift);
+    }
+
+    #[inline(always)]
+    pub fn as_bytes(&self) -> &[u8] {
+        &*self.0
+    }
+}
+
+impl Default for NibbleArray {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, PartialOrd, Ord)]
+pub enum LightChannel {
+    Sky,
+    Block,
+}
+
+impl LightChannel {
+    pub const ALL: [Self; 2] = [Self::Sky, Self::Block];
+}
+
+/// Sky and block light of a single position.
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct LightLevel {
+    pub sky: u8,
+    pub block: u8,
+}
+
+impl LightLevel {
+    /// Light of a position open to the sky, used where no chunk is loaded.
+    pub const FULL_SKY: Self = Self {
+        sky: MAX_LIGHT,
+        block: 0,
+    };
+
+    #[inline(always)]
+    pub const fn new(sky: u8, block: u8) -> Self {
+        Self { sky, block }
+    }
+
+    /// The brighter of both channels.
+    #[inline(always)]
+    pub const fn max(self) -> u8 {
+        if self.sky > self.block {
+            self.sky
+        } else {
+            self.block
+        }
+    }
+}
+
+/// Light of a chunk. `open_sky` marks chunks lit as if nothing was above them, so they can be
+/// corrected once the chunk above loads.
+#[derive(Debug, Default, Clone, Eq, PartialEq)]
+pub struct ChunkLight {
+    pub sky: NibbleArray,
+    pub block: NibbleArray,
+    pub(crate) open_sky: bool,
+}
+
+impl ChunkLight {
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    #[inline(always)]
+    pub fn channel(&self, channel: LightChannel) -> &NibbleArray {
+        match channel {
+            LightChannel::Sky => &self.sky,
+            LightChannel::Block => &self.block,
+        }
+    }
+
+    #[inline(always)]
+    pub fn channel_mut(&mut self, channel: LightChannel) -> &mut NibbleArray {
+        match channel {
+            LightChannel::Sky => &mut self.sky,
+            LightChannel::Block => &mut self.block,
+        }
+    }
+
+    /// Light at a local position, which must be inside the chunk.
+    #[inline(always)]
+    pub fn get(&self, position: IVec3, channel: LightChannel) -> u8 {
+        self.channel(channel).get(linearize(position))
+    }
+
+    #[inline(always)]
+    pub fn set(&mut self, position: IVec3, channel: LightChannel, level: u8) {
+        self.channel_mut(channel).set(linearize(position), level)
+    }
+
+    #[inline(always)]
+    pub fn sample(&self, position: IVec3) -> LightLevel {
+        let index = linearize(position);
+
+        LightLevel::new(self.sky.get(index), self.block.get(index))
+    }
+}
@@ -0,0 +1,40 @@
+use {
+    crate::prelude::{ChunkMap, ChunkPos, ChunkReady, LightEngine, CHUNK_SIZE},
+    bevy::{
+        math::IVec3,
+        prelude::{MessageReader, ResMut},
+    },
+    std::mem::take,
+};
+
+/// Lights chunks that just became ready, then applies the light updates queued by block changes.
+pub fn update_light(mut chunk_map: ResMut<ChunkMap>, mut ready_msg: MessageReader<ChunkReady>) {
+    let ready: Vec<ChunkPos> = ready_msg.read().map(|&ChunkReady(pos)| pos).collect();
+
+    let updates: Vec<(ChunkPos, Vec<IVec3>)> = chunk_map
+        .chunks
+        .iter_mut()
+        .filter(|(_, chunk)| !chunk.light_updates.is_empty())
+        .map(|(&pos, chunk)| (pos, take(&mut chunk.light_updates)))
+        .collect();
+
+    if ready.is_empty() && updates.is_empty() {
+        return;
+    }
+
+    let mut engine = LightEngine::new(&mut chunk_map);
+
+    for pos in ready {
+        engine.light_chunk(pos);
+    }
+
+    for (pos, positions) in updates {
+        let origin: IVec3 = (pos * CHUNK_SIZE).into();
+
+        for position in positions {
+            engine.update_block(origin + position);
+        }
+    }
+
+    engine.finish();
+}
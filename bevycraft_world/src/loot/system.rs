@@ -0,0 +1,60 @@
+use {
+    bevy::{
+        math::IVec3,
+        prelude::{Message, MessageWriter},
+    },
+    bevycraft_core::prelude::{
+        BlockStates, Item, ItemStack, LootContext, LootTables, Registrar, RegistrarOps,
+    },
+    fastrand::Rng,
+};
+
+/// Items dropped by a block broken in the world.
+#[derive(Message, Debug, Clone)]
+pub struct BlockDrops {
+    pub position: IVec3,
+    /// State of the block before it was broken.
+    pub state: usize,
+    pub drops: Vec<ItemStack>,
+}
+
+/// Rolls the loot of the blocks taken from
+/// [`WorldAccess::take_broken`](crate::prelude::WorldAccess::take_broken) and writes a
+/// [`BlockDrops`] for every block that dropped something. Nothing drops without [`LootTables`].
+///
+/// Resolves the broken states through the global [`BlockStates`], which must be built or
+/// installed.
+pub fn emit_block_drops(
+    writer: &mut MessageWriter<BlockDrops>,
+    loot: Option<&LootTables>,
+    rng: &mut Rng,
+    broken: Vec<(IVec3, usize)>,
+) {
+    let Some(loot) = loot else {
+        return;
+    };
+
+    if broken.is_empty() {
+        return;
+    }
+
+    let states = BlockStates::global();
+    let items = Registrar::<Item>::read_from_registry();
+
+    for (position, state) in broken {
+        let context = LootContext {
+            tool: None,
+            seed: rng.u64(..),
+        };
+
+        let drops = loot.block_drops(states.block_of(state), &context, &*items);
+
+        if !drops.is_empty() {
+            writer.write(BlockDrops {
+                position,
+                state,
+                drops,
+            });
+        }
+    }
+}
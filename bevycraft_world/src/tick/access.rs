@@ -0,0 +1,218 @@
+use {
+    crate::prelude::{can_fall_into, ChunkMap, ChunkPos, TickScheduler, CHUNK_SIZE},
+    bevy::math::IVec3,
+    bevycraft_core::{
+        blocks::AIR,
+        prelude::{BlockFlags, BlockLevel, BlockStates, Direction},
+    },
+    std::collections::VecDeque,
+};
+
+/// Neighbor updates handled for a single edit before the rest is dropped, so callbacks that
+/// keep triggering each other cannot hang the world.
+pub const MAX_NEIGHBOR_UPDATES: usize = 1 << 16;
+
+/// [`BlockLevel`] over the loaded chunks of a [`ChunkMap`], handed to block callbacks.
+///
+/// Neighbor updates are queued and run breadth-first by the outermost edit, so callbacks that
+/// edit the world again never recurse.
+///
+/// Creating one panics unless the global [`BlockStates`] is built or installed.
+pub struct WorldAccess<'a> {
+    pub chunk_map: &'a mut ChunkMap,
+    pub scheduler: &'a mut TickScheduler,
+    states: &'static BlockStates,
+    neighbor_updates: VecDeque<(IVec3, IVec3)>,
+    updating: bool,
+    pub(crate) broken: Vec<(IVec3, usize)>,
+    falling: Vec<(IVec3, usize)>,
+}
+
+impl<'a> WorldAccess<'a> {
+    #[inline]
+    pub fn new(chunk_map: &'a mut ChunkMap, scheduler: &'a mut TickScheduler) -> Self {
+        Self {
+            chunk_map,
+            scheduler,
+            states: BlockStates::global(),
+            neighbor_updates: VecDeque::new(),
+            updating: false,
+            broken: Vec::new(),
+            falling: Vec::new(),
+        }
+    }
+
+    /// Takes the positions and states of the blocks broken so far, by
+    /// [`BlockLevel::break_block`] or [`explode`](crate::prelude::explode).
+    ///
+    /// Whoever creates a [`WorldAccess`] is responsible for draining these, usually into
+    /// [`emit_block_drops`](crate::prelude::emit_block_drops); blocks left here drop nothing.
+    #[inline]
+    pub fn take_broken(&mut self) -> Vec<(IVec3, usize)> {
+        std::mem::take(&mut self.broken)
+    }
+
+    /// Takes the positions and states of the blocks that started falling so far, to spawn their
+    /// [`FallingBlock`](crate::prelude::FallingBlock) entities with
+    /// [`spawn_falling_blocks`](crate::prelude::spawn_falling_blocks). Blocks left here are lost.
+    #[inline]
+    pub fn take_falling(&mut self) -> Vec<(IVec3, usize)> {
+        std::mem::take(&mut self.falling)
+    }
+
+    /// Removes the block at `position` to let it fall if it is affected by gravity and nothing
+    /// supports it, returning whether it did. Blocks above unloaded chunks stay in place.
+    pub fn try_fall(&mut self, position: IVec3, state: usize) -> bool {
+        let flags = self.states.flags_of(self.states.block_of(state));
+
+        if !flags.contains(BlockFlags::FALLS) {
+            return false;
+        }
+
+        let supported = self
+            .get_state(position + Direction::NegY.offset())
+            .is_none_or(|below| !can_fall_into(self.states, below));
+
+        if supported {
+            return false;
+        }
+
+        self.set_state(position, AIR.default_state());
+        self.falling.push((position, state));
+        self.notify_neighbors(position);
+
+        true
+    }
+
+    /// Runs the queued neighbor updates, unless an outer call already does.
+    fn run_neighbor_updates(&mut self) {
+        if self.updating {
+            return;
+        }
+
+        self.updating = true;
+
+        let mut handled = 0;
+
+        while let Some((position, neighbor)) = self.neighbor_updates.pop_front() {
+            if handled == MAX_NEIGHBOR_UPDATES {
+                self.neighbor_updates.clear();
+
+                break;
+            }
+
+            handled += 1;
+
+            let Some(state) = self.get_state(position) else {
+                continue;
+            };
+
+            if self.try_fall(position, state) {
+                continue;
+            }
+
+            if let Some(callbacks) = self.states.callbacks_of(self.states.block_of(state)) {
+                callbacks.on_neighbor_changed(self, position, state, neighbor);
+            }
+        }
+
+        self.updating = false;
+    }
+}
+
+impl BlockLevel for WorldAccess<'_> {
+    #[inline]
+    fn get_state(&self, position: IVec3) -> Option<usize> {
+        let (chunk, local) = split_world_pos(position);
+
+        self.chunk_map.get(&chunk)?.get(local)
+    }
+
+    #[inline]
+    fn set_state(&mut self, position: IVec3, state: usize) -> bool {
+        let (chunk, local) = split_world_pos(position);
+
+        let Some(chunk) = self.chunk_map.get_mut(&chunk) else {
+            return false;
+        };
+
+        chunk.set(local, state);
+
+        true
+    }
+
+    #[inline]
+    fn schedule_tick(&mut self, position: IVec3, delay: u32) {
+        self.scheduler.schedule(position, delay);
+    }
+
+    #[inline]
+    fn random(&mut self, bound: u32) -> u32 {
+        self.scheduler.rng.u32(0..bound.max(1))
+    }
+
+    fn place_block(&mut self, position: IVec3, state: usize) -> bool {
+        if !self.set_state(position, state) {
+            return false;
+        }
+
+        if let Some(callbacks) = self.states.callbacks_of(self.states.block_of(state)) {
+            callbacks.on_place(self, position, state);
+        }
+
+        let falling = self
+            .get_state(position)
+            .is_some_and(|state| self.try_fall(position, state));
+
+        if !falling {
+            self.notify_neighbors(position);
+        }
+
+        true
+    }
+
+    fn break_block(&mut self, position: IVec3) -> Option<usize> {
+        let state = self
+            .get_state(position)
+            .filter(|&state| state != AIR.default_state())?;
+
+        if let Some(callbacks) = self.states.callbacks_of(self.states.block_of(state)) {
+            callbacks.on_break(self, position, state);
+        }
+
+        self.set_state(position, AIR.default_state());
+        self.broken.push((position, state));
+        self.notify_neighbors(position);
+
+        Some(state)
+    }
+
+    fn use_block(&mut self, position: IVec3) -> bool {
+        let Some(state) = self.get_state(position) else {
+            return false;
+        };
+
+        self.states
+            .callbacks_of(self.states.block_of(state))
+            .is_some_and(|callbacks| callbacks.on_use(self, position, state))
+    }
+
+    fn notify_neighbors(&mut self, position: IVec3) {
+        self.neighbor_updates.extend(
+            Direction::ALL.map(|direction| (position + direction.offset(), position)),
+        );
+
+        self.run_neighbor_updates();
+    }
+}
+
+/// Splits a world block position into its chunk and the local position inside it.
+#[inline(always)]
+pub fn split_world_pos(position: IVec3) -> (ChunkPos, IVec3) {
+    let size = IVec3::splat(CHUNK_SIZE);
+
+    (
+        ChunkPos::from(position.div_euclid(size)),
+        position.rem_euclid(size),
+    )
+}
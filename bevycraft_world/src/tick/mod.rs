@@ -0,0 +1,3 @@
+pub mod access;
+pub mod scheduler;
+pub mod system;
@@ -0,0 +1,203 @@
+use {
+    crate::prelude::{split_world_pos, ChunkMap, ChunkPos},
+    bevy::{
+        math::IVec3,
+        platform::{
+            collections::{HashMap, HashSet},
+            hash::NoOpHash,
+        },
+        prelude::Resource,
+    },
+    fastrand::Rng,
+    std::{cmp::Ordering, collections::BinaryHeap},
+};
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct ScheduledTick {
+    pub due: u64,
+    pub position: IVec3,
+    order: u64,
+}
+
+impl Ord for ScheduledTick {
+    #[inline(always)]
+    fn cmp(&self, other: &Self) -> Ordering {
+        other
+            .due
+            .cmp(&self.due)
+            .then_with(|| other.order.cmp(&self.order))
+    }
+}
+
+impl PartialOrd for ScheduledTick {
+    #[inline(always)]
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Block ticks of the world, advanced once per `FixedUpdate`.
+///
+/// Scheduled ticks are keyed by position and due tick, so scheduling the same tick twice is a
+/// no-op. Ticks falling due in an unloaded chunk are parked until the chunk loads again.
+#[derive(Resource)]
+pub struct TickScheduler {
+    tick: u64,
+    order: u64,
+    queue: BinaryHeap<ScheduledTick>,
+    keys: HashSet<(IVec3, u64)>,
+    parked: HashMap<ChunkPos, Vec<ScheduledTick>, NoOpHash>,
+    pub(crate) rng: Rng,
+}
+
+impl Default for TickScheduler {
+    #[inline]
+    fn default() -> Self {
+        Self::with_seed(0)
+    }
+}
+
+impl TickScheduler {
+    #[inline]
+    pub fn with_seed(seed: u64) -> Self {
+        Self {
+            tick: 0,
+            order: 0,
+            queue: BinaryHeap::new(),
+            keys: HashSet::default(),
+            parked: HashMap::with_hasher(NoOpHash),
+            rng: Rng::with_seed(seed),
+        }
+    }
+
+    #[inline(always)]
+    pub const fn current_tick(&self) -> u64 {
+        self.tick
+    }
+
+    /// Schedules a tick at `position` in `delay` ticks, at least one.
+    pub fn schedule(&mut self, position: IVec3, delay: u32) {
+        let due = self.tick + delay.max(1) as u64;
+
+        if self.keys.insert((position, due)) {
+            self.push(ScheduledTick {
+                due,
+                position,
+                order: 0,
+            });
+        }
+    }
+
+    #[inline]
+    pub fn is_scheduled(&self, position: IVec3, due: u64) -> bool {
+        self.keys.contains(&(position, due))
+    }
+
+    /// Number of pending ticks, parked ones included.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.keys.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.keys.is_empty()
+    }
+
+    /// Moves to the next tick and returns every tick now due, in scheduling order.
+    pub(crate) fn advance(&mut self) -> Vec<ScheduledTick> {
+        self.tick += 1;
+
+        let mut due = Vec::new();
+
+        while self.queue.peek().is_some_and(|t| t.due <= self.tick) {
+            due.extend(self.queue.pop());
+        }
+
+        due
+    }
+
+    /// Marks a due tick as run.
+    #[inline]
+    pub(crate) fn complete(&mut self, tick: &ScheduledTick) {
+        self.keys.remove(&(tick.position, tick.due));
+    }
+
+    /// Keeps a due tick whose chunk is not loaded until [`Self::restore_loaded`] finds the chunk
+    /// loaded again.
+    pub(crate) fn park(&mut self, tick: ScheduledTick) {
+        let (chunk, _) = split_world_pos(tick.position);
+
+        self.parked.entry(chunk).or_default().push(tick);
+    }
+
+    /// Queues the ticks parked in `chunk` again; they run on the next tick.
+    pub(crate) fn restore(&mut self, chunk: ChunkPos) {
+        for tick in self.parked.remove(&chunk).unwrap_or_default() {
+            self.push(tick);
+        }
+    }
+
+    /// Queues the ticks parked in every chunk of `chunk_map` now loaded again; they run on the
+    /// next tick.
+    pub(crate) fn restore_loaded(&mut self, chunk_map: &ChunkMap) {
+        let loaded: Vec<ChunkPos> = self
+            .parked
+            .keys()
+            .filter(|pos| chunk_map.is_loaded(pos))
+            .copied()
+            .collect();
+
+        for chunk in loaded {
+            self.restore(chunk);
+        }
+    }
+
+    #[inline]
+    fn push(&mut self, mut tick: ScheduledTick) {
+        tick.order = self.order;
+        self.order += 1;
+
+        self.queue.push(tick);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, crate::prelude::Chunk};
+
+    #[test]
+    fn parked_tick_runs_once_its_chunk_is_loaded() {
+        let mut scheduler = TickScheduler::default();
+        let mut chunk_map = ChunkMap::new(1);
+        let position = IVec3::new(3, 4, 5);
+        let (chunk, _) = split_world_pos(position);
+
+        scheduler.schedule(position, 1);
+
+        // The chunk is unloaded when the tick falls due.
+        scheduler.restore_loaded(&chunk_map);
+
+        for tick in scheduler.advance() {
+            scheduler.park(tick);
+        }
+
+        scheduler.restore_loaded(&chunk_map);
+
+        assert!(scheduler.advance().is_empty());
+        assert_eq!(scheduler.len(), 1);
+
+        // The chunk loads again without anyone restoring it by hand.
+        chunk_map.chunks.insert(chunk, Chunk::empty());
+        scheduler.restore_loaded(&chunk_map);
+
+        let due = scheduler.advance();
+
+        assert_eq!(due.len(), 1);
+        assert_eq!(due[0].position, position);
+
+        scheduler.complete(&due[0]);
+
+        assert!(scheduler.is_empty());
+    }
+}
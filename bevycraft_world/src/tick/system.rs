@@ -0,0 +1,97 @@
+use {
+    crate::{
+        falling::system::spawn_falling_blocks,
+        prelude::{
+            emit_block_drops, BlockDrops, ChunkMap, ChunkPos, TickScheduler, WorldAccess,
+            CHUNK_SIZE,
+        },
+    },
+    bevy::{
+        math::IVec3,
+        prelude::{Commands, MessageWriter, Res, ResMut, Resource},
+    },
+    bevycraft_core::prelude::{BlockLevel, BlockStates, LootTables},
+};
+
+#[derive(Resource)]
+pub struct TickConfig {
+    /// Random ticks given to every loaded chunk per tick.
+    pub random_ticks: u32,
+}
+
+impl Default for TickConfig {
+    fn default() -> Self {
+        Self { random_ticks: 3 }
+    }
+}
+
+/// Runs the scheduled ticks now due, then the random ticks of every loaded chunk.
+///
+/// Ticks parked in an unloaded chunk are queued again on the first run that finds the chunk
+/// loaded.
+///
+/// Runs after [`RegistrySet::Freeze`](bevycraft_core::prelude::RegistrySet::Freeze), which
+/// builds the global [`BlockStates`] it reads.
+pub fn tick_blocks(
+    mut commands: Commands,
+    mut chunk_map: ResMut<ChunkMap>,
+    mut scheduler: ResMut<TickScheduler>,
+    mut drops_msg: MessageWriter<BlockDrops>,
+    config: Res<TickConfig>,
+    loot: Option<Res<LootTables>>,
+) {
+    let states = BlockStates::global();
+
+    scheduler.restore_loaded(&chunk_map);
+
+    let due = scheduler.advance();
+
+    let chunks: Vec<ChunkPos> = chunk_map
+        .chunks
+        .iter()
+        .filter(|(_, chunk)| !chunk.storage.is_empty())
+        .map(|(&pos, _)| pos)
+        .collect();
+
+    let mut level = WorldAccess::new(&mut chunk_map, &mut scheduler);
+
+    for tick in due {
+        let Some(state) = level.get_state(tick.position) else {
+            level.scheduler.park(tick);
+
+            continue;
+        };
+
+        level.scheduler.complete(&tick);
+
+        if let Some(ticker) = states.ticker_of(states.block_of(state)) {
+            ticker.scheduled_tick(&mut level, tick.position, state);
+        }
+    }
+
+    for pos in chunks {
+        let origin: IVec3 = (pos * CHUNK_SIZE).into();
+
+        for _ in 0..config.random_ticks {
+            let local = IVec3::new(
+                level.random(CHUNK_SIZE as u32) as i32,
+                level.random(CHUNK_SIZE as u32) as i32,
+                level.random(CHUNK_SIZE as u32) as i32,
+            );
+            let position = origin + local;
+
+            let Some(state) = level.get_state(position) else {
+                break;
+            };
+
+            if let Some(ticker) = states.ticker_of(states.block_of(state)) {
+                ticker.random_tick(&mut level, position, state);
+            }
+        }
+    }
+
+    spawn_falling_blocks(&mut commands, level.take_falling());
+
+    let broken = level.take_broken();
+    emit_block_drops(&mut drops_msg, loot.as_deref(), &mut level.scheduler.rng, broken);
+}